@@ -1,4 +1,4 @@
 <?xml version="1.0" encoding="UTF-8"?>
-<tileset version="1.2" tiledversion="1.3.1" name="floors" tilewidth="256" tileheight="48" tilecount="15" columns="1">
- <image source="floors.png" width="256" height="720"/>
+<tileset version="1.2" tiledversion="1.3.1" name="floors" tilewidth="256" tileheight="48" tilecount="16" columns="1">
+ <image source="floors.png" width="256" height="768"/>
 </tileset>
@@ -0,0 +1,52 @@
+use std::path::Path;
+
+use amethyst::utils::application_root_dir;
+
+use controls::Recording;
+use elevator_main::headless::{Simulation, DEFAULT_TIMESTEP};
+
+const DEFAULT_FRAMES: u64 = 600;
+const DEFAULT_SEED: u64 = 0;
+
+/// Runs the game without a window for a number of frames (the first argument) and prints
+/// where the player ended up. When the first argument is a recording file instead, the
+/// whole recording is replayed.
+fn main() -> amethyst::Result<()> {
+    amethyst::start_logger(Default::default());
+
+    let main_root = application_root_dir()?;
+    let app_root = main_root.parent().unwrap();
+    let assets_dir = app_root.join("assets");
+
+    let arg = std::env::args().nth(1);
+    let (mut simulation, frames) = match arg {
+        Some(ref path) if path.parse::<u64>().is_err() => {
+            let recording = Recording::load(Path::new(path))?;
+            let simulation =
+                Simulation::from_recording(&assets_dir, "tilesets/floors_1.json", &recording)?;
+            let frames = simulation.recorded_frames() as u64;
+            (simulation, frames)
+        }
+        _ => {
+            let simulation = Simulation::new(
+                &assets_dir,
+                "tilesets/floors_1.json",
+                Vec::new(),
+                DEFAULT_TIMESTEP,
+                DEFAULT_SEED,
+            )?;
+            let frames = arg
+                .and_then(|frames| frames.parse::<u64>().ok())
+                .unwrap_or(DEFAULT_FRAMES);
+            (simulation, frames)
+        }
+    };
+    simulation.run(frames);
+
+    println!(
+        "### Ran {} frames, player position: {:?} ###",
+        frames,
+        simulation.player_position()
+    );
+    Ok(())
+}
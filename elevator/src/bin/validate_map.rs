@@ -0,0 +1,41 @@
+use std::{fs, fs::File, process};
+
+use amethyst::{assets::Format, utils::application_root_dir};
+
+use map::{validate, Map, TmxFormat};
+
+const DEFAULT_MAP: &str = "tilesets/floors_1.json";
+
+/// Checks the maps given as arguments (relative to the assets directory, json or tmx) and
+/// prints every problem found. Exits with a non-zero status when there are any.
+fn main() -> amethyst::Result<()> {
+    let main_root = application_root_dir()?;
+    let app_root = main_root.parent().unwrap();
+    let assets_dir = app_root.join("assets");
+
+    let mut map_paths: Vec<String> = std::env::args().skip(1).collect();
+    if map_paths.is_empty() {
+        map_paths.push(String::from(DEFAULT_MAP));
+    }
+
+    let mut problem_count = 0;
+    for map_path in map_paths.iter() {
+        let path = assets_dir.join(map_path);
+        let map: Map = if map_path.ends_with(".tmx") {
+            TmxFormat.import_simple(fs::read(&path)?)?
+        } else {
+            serde_json::from_reader(File::open(&path)?)?
+        };
+        let problems = validate(&map);
+        for problem in problems.iter() {
+            println!("{}: {}", map_path, problem);
+        }
+        println!("### {}: {} problem(s) ###", map_path, problems.len());
+        problem_count += problems.len();
+    }
+
+    if problem_count > 0 {
+        process::exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,300 @@
+use amethyst::{
+    core::{SystemBundle, SystemDesc, SystemExt},
+    ecs::{DispatcherBuilder, World},
+    error::Error,
+};
+
+use crate::headless::DEFAULT_TIMESTEP;
+use controls::{
+    systems::{
+        ControlRecorderSystem, InputControlsSystem, ReplayTimeSystem, ScriptedControlsSystem,
+    },
+    ControlInput, Recording,
+};
+use door::systems::{
+    DoorEntryCollisionSystem, DoorTransformationSystem, EnemySpawnSystem, RoomSystem,
+};
+use elevator::systems::{
+    ElevatorCallSystem, ElevatorControlSystem, ElevatorCrushSystem, ElevatorTransformationSystem,
+};
+use enemy::systems::{EnemyAISystem, EnemyCombatSystem};
+use escalator::systems::EscalatorSystem;
+use lamp::systems::LampSystem;
+use level::GameplayState;
+use map::systems::MapRenderSystem;
+use person::systems::*;
+use physics::systems::*;
+use player::systems::*;
+use score::systems::ScoreSystemDesc;
+
+/// Where the `ControlInput` for each frame comes from.
+pub enum ControlsSource {
+    /// the keyboard, through the `InputBundle` bindings
+    Input,
+    /// a fixed list of inputs, one per frame
+    Scripted(Vec<ControlInput>),
+    /// the inputs of a recording, with `Time` following the recorded length of every frame
+    Replay(Recording),
+}
+
+/// All of the systems that make up the game itself, without rendering, animation or ui.
+///
+/// The systems that move things, spawn enemies or resolve collisions only run while the
+/// `GameplayState` is `Running`, the level is still drawn and tracked while it is paused.
+pub struct GameplayBundle {
+    controls: ControlsSource,
+    record: bool,
+    menu_input: bool,
+}
+
+impl GameplayBundle {
+    pub fn new(controls: ControlsSource) -> Self {
+        GameplayBundle {
+            controls,
+            record: false,
+            menu_input: false,
+        }
+    }
+
+    /// Records the controls of every frame into the `Recording` resource.
+    pub fn with_recorder(mut self) -> Self {
+        self.record = true;
+        self
+    }
+
+    /// Reads the keyboard for the menus while the gameplay is paused, for when the controls
+    /// come from a replay.
+    pub fn with_menu_input(mut self) -> Self {
+        self.menu_input = true;
+        self
+    }
+}
+
+impl<'a, 'b> SystemBundle<'a, 'b> for GameplayBundle {
+    fn build(
+        self,
+        world: &mut World,
+        builder: &mut DispatcherBuilder<'a, 'b>,
+    ) -> Result<(), Error> {
+        match self.controls {
+            ControlsSource::Input => {
+                builder.add(
+                    InputControlsSystem,
+                    "input_controls_system",
+                    &["input_system"],
+                );
+            }
+            ControlsSource::Scripted(script) => {
+                builder.add(
+                    ScriptedControlsSystem::new(script).pausable(GameplayState::Running),
+                    "input_controls_system",
+                    &[],
+                );
+            }
+            ControlsSource::Replay(recording) => {
+                builder.add(
+                    ScriptedControlsSystem::new(recording.inputs())
+                        .pausable(GameplayState::Running),
+                    "input_controls_system",
+                    &[],
+                );
+                builder.add(
+                    ReplayTimeSystem::new(recording.frames, DEFAULT_TIMESTEP)
+                        .pausable(GameplayState::Running),
+                    "replay_time_system",
+                    &[],
+                );
+                // everything after this sees the recorded frame length
+                builder.add_barrier();
+            }
+        }
+        if self.menu_input {
+            builder.add(
+                InputControlsSystem.pausable(GameplayState::Paused),
+                "menu_controls_system",
+                &["input_system"],
+            );
+        }
+        // only frames that are actually played are recorded, so a replay lines up with them
+        if self.record {
+            builder.add(
+                ControlRecorderSystem::default().pausable(GameplayState::Running),
+                "control_recorder_system",
+                &["input_controls_system"],
+            );
+        }
+        builder.add(PersonFloorSystem, "person_floor_system", &[]);
+        builder.add(MapRenderSystem::default(), "map_render_system", &[]);
+        builder.add(
+            EnemySpawnSystem.pausable(GameplayState::Running),
+            "enemy_spawn_system",
+            &[],
+        );
+        builder.add(
+            PlayerControlsSystem.pausable(GameplayState::Running),
+            "player_controls_system",
+            &["input_controls_system"],
+        );
+        builder.add(
+            PlayerGunControlsSystem.pausable(GameplayState::Running),
+            "player_gun_controls_system",
+            &["input_controls_system"],
+        );
+        builder.add(
+            ElevatorCallSystem.pausable(GameplayState::Running),
+            "elevator_call_system",
+            &["player_controls_system"],
+        );
+        builder.add(
+            ElevatorControlSystem.pausable(GameplayState::Running),
+            "elevator_control_system",
+            &["input_controls_system", "elevator_call_system"],
+        );
+        builder.add(
+            EscalatorSystem.pausable(GameplayState::Running),
+            "escalator_system",
+            &["player_controls_system"],
+        );
+        builder.add(
+            PersonKinematicsSystem.pausable(GameplayState::Running),
+            "person_kinematics_system",
+            &["escalator_system"],
+        );
+        builder.add(
+            KinematicsSystem.pausable(GameplayState::Running),
+            "kinematics_system",
+            &["person_kinematics_system"],
+        );
+        builder.add(
+            ShootSystem.pausable(GameplayState::Running),
+            "shoot_system",
+            &["kinematics_system"],
+        );
+        // PincerAi
+        builder.add(
+            CollisionSystem.pausable(GameplayState::Running),
+            "collision_system",
+            &["shoot_system"],
+        );
+        builder.add(
+            BulletCollisionSystem.pausable(GameplayState::Running),
+            "bullet_collision_system",
+            &["collision_system"],
+        );
+        builder.add(
+            ElevatorCrushSystem.pausable(GameplayState::Running),
+            "elevator_crush_system",
+            &["collision_system"],
+        );
+        builder.add(
+            MeleeSystem.pausable(GameplayState::Running),
+            "melee_system",
+            &["collision_system"],
+        );
+        builder.add(
+            LampSystem.pausable(GameplayState::Running),
+            "lamp_system",
+            &["bullet_collision_system"],
+        );
+        builder.add(
+            BulletLifetimeSystem.pausable(GameplayState::Running),
+            "bullet_lifetime_system",
+            &["bullet_collision_system", "lamp_system"],
+        );
+        builder.add(
+            DoorEntryCollisionSystem.pausable(GameplayState::Running),
+            "door_entry_collision_system",
+            &[],
+        );
+        builder.add(
+            RoomSystem.pausable(GameplayState::Running),
+            "room_system",
+            &["door_entry_collision_system"],
+        );
+        builder.add(
+            PlayerDeathSystem.pausable(GameplayState::Running),
+            "player_death_system",
+            &[
+                "person_floor_system",
+                "bullet_collision_system",
+                "elevator_crush_system",
+                "lamp_system",
+            ],
+        );
+        builder.add(
+            LevelExitSystem.pausable(GameplayState::Running),
+            "level_exit_system",
+            &["person_floor_system"],
+        );
+        builder.add(
+            DifficultySystem.pausable(GameplayState::Running),
+            "difficulty_system",
+            &["person_floor_system", "player_death_system"],
+        );
+        builder.add(
+            ScoreSystemDesc::default().build(world),
+            "score_system",
+            &[
+                "bullet_collision_system",
+                "elevator_crush_system",
+                "lamp_system",
+                "room_system",
+            ],
+        );
+        // PincerCollision
+        // MarineCollision
+        builder.add(
+            DefaultTransformationSystem,
+            "default_transformation_system",
+            &[],
+        );
+        builder.add(DoorTransformationSystem, "door_transformation_system", &[]);
+        builder.add(
+            ElevatorTransformationSystem,
+            "elevator_transformation_system",
+            &[],
+        );
+        builder.add(
+            PersonTransformationSystem,
+            "person_transformation_system",
+            &["elevator_transformation_system"],
+        );
+        builder.add(
+            CameraTransformationSystem,
+            "camera_transformation_system",
+            &["person_transformation_system"],
+        );
+        builder.add(
+            EnemyAISystem.pausable(GameplayState::Running),
+            "enemy_ai_system",
+            &[],
+        );
+        builder.add(
+            EnemyCombatSystem.pausable(GameplayState::Running),
+            "enemy_combat_system",
+            &["enemy_ai_system"],
+        );
+        builder.add(
+            ProximitySystem.pausable(GameplayState::Running),
+            "proximity_system",
+            &[
+                "elevator_transformation_system",
+                "person_transformation_system",
+            ],
+        );
+        builder.add(
+            GunTransformationSystem,
+            "gun_transformation_system",
+            &[
+                "default_transformation_system",
+                "person_transformation_system",
+            ],
+        );
+        builder.add(
+            DirectionSystem,
+            "direction_system",
+            &["person_transformation_system"], //"gun_transformations_system"],
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+use std::{fs, fs::File, path::Path, sync::Arc};
+
+use amethyst::{
+    assets::{AssetStorage, Format, Handle, Loader, Prefab},
+    core::{math::Vector2, ArcThreadPool, Time},
+    ecs::{Join, World, WorldExt},
+    renderer::{transparent::Transparent, SpriteRender, SpriteSheet, Texture},
+    DataInit, GameData, GameDataBuilder,
+};
+use rayon::ThreadPoolBuilder;
+
+use crate::bundle::{ControlsSource, GameplayBundle};
+use animation::components::{Animation, AnimationPrefabData};
+use asset::{load_assets, load_prefab_paths, AssetType, PrefabList};
+use camera::init_camera;
+use controls::{ControlInput, Recording};
+use enemy::{BehaviourTrees, EnemyArchetypes};
+use level::{DifficultyConfig, LevelProgress};
+use map::{Map, TmxFormat};
+use person::{components::Person, Weapons};
+use player::{components::Player, load_player};
+use random::GameRng;
+
+/// The fixed timestep used when none is given, matching a 60 fps game.
+pub const DEFAULT_TIMESTEP: f32 = 1. / 60.;
+
+/// Runs the game loop without a window, renderer or keyboard.
+///
+/// Every frame advances `Time` by a fixed timestep and the controls come from a script, so
+/// the same script always produces the same world.
+pub struct Simulation {
+    world: World,
+    game_data: GameData<'static, 'static>,
+    timestep: f32,
+    recorded_frames: usize,
+}
+
+impl Simulation {
+    /// Loads the map at `map_path` (relative to the assets directory) and spawns the player.
+    pub fn new(
+        assets_dir: &Path,
+        map_path: &str,
+        script: Vec<ControlInput>,
+        timestep: f32,
+        seed: u64,
+    ) -> amethyst::Result<Self> {
+        Simulation::with_controls(
+            assets_dir,
+            map_path,
+            ControlsSource::Scripted(script),
+            timestep,
+            seed,
+        )
+    }
+
+    /// Replays a recorded session, using the recorded length of each frame and its seed
+    /// (or zero for recordings without one).
+    pub fn from_recording(
+        assets_dir: &Path,
+        map_path: &str,
+        recording: &Recording,
+    ) -> amethyst::Result<Self> {
+        let mut simulation = Simulation::with_controls(
+            assets_dir,
+            map_path,
+            ControlsSource::Replay(recording.clone()),
+            DEFAULT_TIMESTEP,
+            recording.seed.unwrap_or_default(),
+        )?;
+        simulation.recorded_frames = recording.frames.len();
+        Ok(simulation)
+    }
+
+    fn with_controls(
+        assets_dir: &Path,
+        map_path: &str,
+        controls: ControlsSource,
+        timestep: f32,
+        seed: u64,
+    ) -> amethyst::Result<Self> {
+        let mut world = World::new();
+        let pool: ArcThreadPool = Arc::new(ThreadPoolBuilder::new().num_threads(1).build()?);
+        world.insert(Loader::new(assets_dir, pool.clone()));
+        world.insert(pool);
+        world.insert(Time::default());
+        world.insert(GameRng::new(seed));
+        world.insert(Recording {
+            seed: Some(seed),
+            frames: Vec::new(),
+        });
+        let enemy_archetypes = EnemyArchetypes::load(&assets_dir.join("enemies.ron"))?;
+        let behaviour_trees = BehaviourTrees::load(&assets_dir.join("behaviours.ron"))?;
+        behaviour_trees.check(&enemy_archetypes)?;
+        world.insert(enemy_archetypes);
+        world.insert(behaviour_trees);
+        world.insert(Weapons::load(&assets_dir.join("weapons.ron"))?);
+        let config_dir = assets_dir.parent().unwrap_or(assets_dir).join("config");
+        world.insert(DifficultyConfig::load(&config_dir.join("difficulty.ron"))?.difficulty(None)?);
+        world.insert(AssetStorage::<Texture>::default());
+        world.insert(AssetStorage::<SpriteSheet>::default());
+
+        // these are only used for rendering, but entities are still created with them
+        world.register::<Animation>();
+        world.register::<Handle<Prefab<AnimationPrefabData>>>();
+        world.register::<SpriteRender>();
+        world.register::<Transparent>();
+
+        let game_data = GameDataBuilder::default()
+            .with_bundle(GameplayBundle::new(controls).with_recorder())?
+            .build(&mut world);
+
+        // the textures and prefabs are never processed, but the handles are still needed
+        let mut progress_counter = load_assets(
+            &mut world,
+            vec![
+                AssetType::Bullet,
+                AssetType::BulletImpact,
+                AssetType::Door,
+                AssetType::Elevator,
+                AssetType::Enemy,
+                AssetType::Guns,
+                AssetType::Lamp,
+                AssetType::Player,
+            ],
+        );
+        let enemy_prefabs = world.read_resource::<EnemyArchetypes>().prefabs();
+        load_prefab_paths(&mut world, enemy_prefabs, &mut progress_counter);
+
+        let mut map: Map = if map_path.ends_with(".tmx") {
+            TmxFormat.import_simple(fs::read(assets_dir.join(map_path))?)?
+        } else {
+            serde_json::from_reader(File::open(assets_dir.join(map_path))?)?
+        };
+        world.insert(LevelProgress::new(map.red_door_count(), 0));
+        map.init_floors(&mut world);
+        world.insert(map);
+
+        let (player_prefab_handle, guns_prefab_handle) = {
+            let prefab_list = world.read_resource::<PrefabList>();
+            (
+                prefab_list.get(AssetType::Player).unwrap().clone(),
+                prefab_list.get(AssetType::Guns).unwrap().clone(),
+            )
+        };
+        load_player(&mut world, player_prefab_handle, guns_prefab_handle);
+        init_camera(&mut world);
+
+        Ok(Simulation {
+            world,
+            game_data,
+            timestep,
+            recorded_frames: 0,
+        })
+    }
+
+    /// The number of recorded frames, zero when not replaying a recording.
+    pub fn recorded_frames(&self) -> usize {
+        self.recorded_frames
+    }
+
+    /// Everything played so far, replaying it gives the same world.
+    pub fn recording(&self) -> Recording {
+        self.world.read_resource::<Recording>().clone()
+    }
+
+    /// Advances the game by a single frame, when replaying a recording the
+    /// `ReplayTimeSystem` swaps the timestep for the recorded one.
+    pub fn step(&mut self) {
+        {
+            let mut time = self.world.write_resource::<Time>();
+            time.set_delta_seconds(self.timestep);
+            time.increment_frame_number();
+        }
+        self.game_data.update(&self.world);
+        self.world.maintain();
+    }
+
+    /// Advances the game by `frames` frames and returns the resulting world.
+    pub fn run(&mut self, frames: u64) -> &World {
+        for _ in 0..frames {
+            self.step();
+        }
+        &self.world
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    pub fn player_position(&self) -> Option<Vector2<f32>> {
+        let persons = self.world.read_storage::<Person>();
+        let players = self.world.read_storage::<Player>();
+        (&persons, &players)
+            .join()
+            .map(|(person, _)| person.position)
+            .next()
+    }
+}
@@ -0,0 +1,4 @@
+pub mod bundle;
+pub mod headless;
+pub mod options;
+pub mod states;
@@ -1,5 +1,3 @@
-mod states;
-
 use amethyst::{
     animation::AnimationBundle,
     assets::{PrefabLoaderSystemDesc, Processor},
@@ -20,16 +18,24 @@ use animation::{
     components::{AnimationId, AnimationPrefabData},
     systems::AnimationControlSystem,
 };
-use door::systems::{
-    DoorAnimationSystem, DoorEntryCollisionSystem, DoorTransformationSystem, EnemySpawnSystem,
+use controls::Recording;
+use door::systems::DoorAnimationSystem;
+use elevator_main::{
+    bundle::{ControlsSource, GameplayBundle},
+    options::LaunchOptions,
+    states,
 };
-use elevator::systems::{ElevatorControlSystem, ElevatorTransformationSystem};
-use enemy::systems::EnemyAISystem;
+use enemy::{BehaviourDebug, BehaviourTrees, EnemyArchetypes};
 use fps::systems::UiFpsSystem;
-use map::{systems::MapRenderSystem, Map, Tileset};
-use person::systems::*;
-use physics::systems::*;
-use player::systems::*;
+use level::{DifficultyConfig, LevelManifest};
+use map::{Map, Tileset};
+use person::{
+    systems::{BulletImpactAnimationSystem, GunAnimationSystem, PersonAnimationSystem},
+    Weapons,
+};
+use player::systems::{LivesHudSystem, WeaponHudSystem};
+use random::GameRng;
+use score::{systems::ScoreHudSystem, HighScores, Score};
 
 fn main() -> amethyst::Result<()> {
     // start logging in amethyst
@@ -40,10 +46,46 @@ fn main() -> amethyst::Result<()> {
     let config_dir = app_root.join("config");
     let display_config_path = config_dir.join("display.ron");
     let assets_dir = app_root.join("assets");
+    let level_manifest = LevelManifest::load(&assets_dir.join("levels.ron"))?;
+    let enemy_archetypes = EnemyArchetypes::load(&assets_dir.join("enemies.ron"))?;
+    let behaviour_trees = BehaviourTrees::load(&assets_dir.join("behaviours.ron"))?;
+    behaviour_trees.check(&enemy_archetypes)?;
+    let weapons = Weapons::load(&assets_dir.join("weapons.ron"))?;
     let binding_path = app_root.join("config").join("bindings.ron");
     let input_bundle =
         InputBundle::<StringBindings>::new().with_bindings_from_file(binding_path)?;
 
+    let options = LaunchOptions::from_args();
+    let mut seed = options.seed;
+    let controls = match &options.replay {
+        Some(path) => {
+            let recording = Recording::load(path)?;
+            println!(
+                "### Replaying {} frames from {:?} ###",
+                recording.frames.len(),
+                path
+            );
+            seed = seed.or(recording.seed);
+            ControlsSource::Replay(recording)
+        }
+        None => ControlsSource::Input,
+    };
+    let game_rng = seed.map(GameRng::new).unwrap_or_default();
+    let behaviour_debug = BehaviourDebug {
+        enabled: options.debug_ai,
+    };
+    let difficulty = DifficultyConfig::load(&config_dir.join("difficulty.ron"))?
+        .difficulty(options.difficulty.as_ref().map(String::as_str))?;
+    println!("### Using difficulty {} ###", difficulty.name);
+    println!("### Using seed {} ###", game_rng.seed());
+    let mut gameplay_bundle = GameplayBundle::new(controls);
+    if options.record.is_some() {
+        gameplay_bundle = gameplay_bundle.with_recorder();
+    }
+    if options.replay.is_some() {
+        gameplay_bundle = gameplay_bundle.with_menu_input();
+    }
+
     let game_data = GameDataBuilder::default()
         .with_system_desc(
             PrefabLoaderSystemDesc::<AnimationPrefabData>::default(),
@@ -62,73 +104,12 @@ fn main() -> amethyst::Result<()> {
         .with_bundle(FpsCounterBundle {})?
         .with_bundle(UiBundle::<StringBindings>::new())?
         .with(UiFpsSystem::default(), "ui_fps_system", &[])
+        .with(ScoreHudSystem::default(), "score_hud_system", &[])
+        .with(LivesHudSystem::default(), "lives_hud_system", &[])
+        .with(WeaponHudSystem::default(), "weapon_hud_system", &[])
         .with(Processor::<Tileset>::new(), "tileset_processor", &[])
         .with(Processor::<Map>::new(), "map_processor", &[])
-        .with(PersonFloorSystem, "person_floor_system", &[])
-        .with(MapRenderSystem, "map_render_system", &[])
-        .with(EnemySpawnSystem, "enemy_spawn_system", &[])
-        .with(PlayerControlsSystem, "player_controls_system", &[])
-        .with(PlayerGunControlsSystem, "player_gun_controls_system", &[])
-        .with(ElevatorControlSystem, "elevator_control_system", &[])
-        .with(
-            PersonKinematicsSystem,
-            "person_kinematics_system",
-            &["player_controls_system"],
-        )
-        .with(
-            KinematicsSystem,
-            "kinematics_system",
-            &["person_kinematics_system"],
-        )
-        .with(ShootSystem, "shoot_system", &["kinematics_system"])
-        // PincerAi
-        .with(CollisionSystem, "collision_system", &["shoot_system"])
-        .with(
-            BulletCollisionSystem,
-            "bullet_collision_system",
-            &["collision_system"],
-        )
-        .with(DoorEntryCollisionSystem, "door_entry_collision_system", &[])
-        // PincerCollision
-        // MarineCollision
-        .with(
-            DefaultTransformationSystem,
-            "default_transformation_system",
-            &[],
-        )
-        .with(DoorTransformationSystem, "door_transformation_system", &[])
-        .with(
-            ElevatorTransformationSystem,
-            "elevator_transformation_system",
-            &[],
-        )
-        .with(
-            PersonTransformationSystem,
-            "person_transformation_system",
-            &["elevator_transformation_system"],
-        )
-        .with(
-            CameraTransformationSystem,
-            "camera_transformation_system",
-            &["person_transformation_system"],
-        )
-        .with(EnemyAISystem, "enemy_ai_system", &[])
-        .with(
-            ProximitySystem,
-            "proximity_system",
-            &[
-                "elevator_transformation_system",
-                "person_transformation_system",
-            ],
-        )
-        .with(
-            GunTransformationSystem,
-            "gun_transformation_system",
-            &[
-                "default_transformation_system",
-                "person_transformation_system",
-            ],
-        )
+        .with_bundle(gameplay_bundle)?
         // BulletTransformation
         // BulletImpact
         .with(
@@ -144,11 +125,6 @@ fn main() -> amethyst::Result<()> {
             "animation_control_system",
             &["person_animation_system", "gun_animation_system"],
         )
-        .with(
-            DirectionSystem,
-            "direction_system",
-            &["person_transformation_system"], //"gun_transformations_system"],
-        )
         .with_bundle(
             RenderingBundle::<DefaultBackend>::new()
                 .with_plugin(
@@ -159,8 +135,20 @@ fn main() -> amethyst::Result<()> {
                 .with_plugin(RenderUi::default()),
         )?;
 
-    let mut game =
-        Application::build(assets_dir, states::GameState::default())?.build(game_data)?;
+    let high_scores = HighScores::load_or_default();
+    let score = Score::new(high_scores.best());
+    let mut game = Application::build(assets_dir, states::TitleState::default())?
+        .with_resource(game_rng)
+        .with_resource(level_manifest)
+        .with_resource(enemy_archetypes)
+        .with_resource(behaviour_trees)
+        .with_resource(weapons)
+        .with_resource(behaviour_debug)
+        .with_resource(difficulty)
+        .with_resource(high_scores)
+        .with_resource(score)
+        .with_resource(options)
+        .build(game_data)?;
     game.run();
 
     Ok(())
@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+/// Options passed on the command line when starting the game.
+#[derive(Debug, Default)]
+pub struct LaunchOptions {
+    /// save the controls of the session to this file when the game exits
+    pub record: Option<PathBuf>,
+    /// play back the controls from this file instead of the keyboard
+    pub replay: Option<PathBuf>,
+    /// seed for gameplay randomness, a random one is picked when not given
+    pub seed: Option<u64>,
+    /// print the decisions of the enemy behaviour trees
+    pub debug_ai: bool,
+    /// preset from `config/difficulty.ron`, its default preset when not given
+    pub difficulty: Option<String>,
+}
+
+impl LaunchOptions {
+    /// Reads `--record <file>`, `--replay <file>`, `--seed <number>`, `--difficulty <preset>` and
+    /// `--debug-ai` from the process arguments.
+    pub fn from_args() -> Self {
+        let mut options = LaunchOptions::default();
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_ref() {
+                "--record" => options.record = args.next().map(PathBuf::from),
+                "--replay" => options.replay = args.next().map(PathBuf::from),
+                "--seed" => options.seed = args.next().and_then(|seed| seed.parse().ok()),
+                "--difficulty" => options.difficulty = args.next(),
+                "--debug-ai" => options.debug_ai = true,
+                _ => println!("Ignoring unknown argument: {}", arg),
+            }
+        }
+        options
+    }
+}
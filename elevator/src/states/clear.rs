@@ -0,0 +1,35 @@
+use amethyst::{core::timing::Time, ecs::Entity, prelude::*};
+
+use crate::states::{message::create_message, LoadingState};
+use level::{CurrentLevel, GameplayState};
+
+const CLEAR_SECONDS: f32 = 3.;
+
+/// Shown after the player escapes a building, then the next one is loaded.
+#[derive(Default)]
+pub struct LevelClearState {
+    message: Option<Entity>,
+    elapsed_seconds: f32,
+}
+
+impl SimpleState for LevelClearState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        println!("### LevelClearState on_start ###");
+        let world = data.world;
+        world.insert(GameplayState::Paused);
+        self.message = Some(create_message(world, "BUILDING CLEARED"));
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        self.elapsed_seconds += data.world.read_resource::<Time>().delta_seconds();
+        if self.elapsed_seconds < CLEAR_SECONDS {
+            return Trans::None;
+        }
+
+        // everything in the world belongs to the finished level, including the camera and ui
+        self.message = None;
+        data.world.delete_all();
+        let next_level = data.world.read_resource::<CurrentLevel>().next();
+        Trans::Switch(Box::new(LoadingState::new(next_level)))
+    }
+}
@@ -1,109 +1,63 @@
-use amethyst::{
-    assets::{AssetStorage, Handle, JsonFormat, Loader, ProgressCounter},
-    prelude::*,
-    ui::UiCreator,
-};
+use amethyst::{core::timing::Time, prelude::*, shrev::EventChannel};
 
-use asset::{load_assets, AssetType, PrefabList};
-use camera::init_camera;
-use map::{Map, Tileset};
-use player::load_player;
+use crate::{
+    options::LaunchOptions,
+    states::{GameOverState, InitialsEntryState, LevelClearState},
+};
+use controls::Recording;
+use level::{GameplayState, LevelProgress};
+use player::Lives;
+use random::GameRng;
+use score::{HighScores, Score, ScoreEvent};
 
-/// state struct for the game state
+/// state struct for the game state, the level is loaded by the time it starts
 #[derive(Default)]
 pub struct GameState {
-    progress_counter: Option<ProgressCounter>,
-    map_handle: Option<Handle<Map>>,
-    tileset_handle: Option<Handle<Tileset>>,
+    elapsed_seconds: f32,
 }
 
 impl SimpleState for GameState {
     fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
         println!("### GameState on_start ###");
-        let world = data.world;
-
-        self.progress_counter = Some(load_assets(
-            world,
-            vec![
-                AssetType::Bullet,
-                AssetType::BulletImpact,
-                AssetType::Door,
-                AssetType::Elevator,
-                AssetType::Enemy,
-                AssetType::Guns,
-                AssetType::Player,
-            ],
-        ));
-
-        let mut progress = ProgressCounter::default();
-        world.exec(|mut creator: UiCreator<'_>| creator.create("ui/fps.ron", &mut progress));
-
-        self.map_handle = {
-            let loader = world.read_resource::<Loader>();
-            Some(loader.load(
-                "tilesets/floors_1.json",
-                JsonFormat,
-                self.progress_counter.as_mut().expect("map"),
-                &world.read_resource::<AssetStorage<Map>>(),
-            ))
-        };
-
-        self.tileset_handle = {
-            let loader = world.read_resource::<Loader>();
-            Some(loader.load(
-                "tilesets/floors.json",
-                JsonFormat,
-                self.progress_counter.as_mut().expect("tileset"),
-                &world.read_resource::<AssetStorage<Tileset>>(),
-            ))
-        };
+        data.world.insert(GameplayState::Running);
+    }
 
-        init_camera(world);
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        // save everything recorded so far, the recording keeps going through the next levels
+        let recording_path = data.world.read_resource::<LaunchOptions>().record.clone();
+        if let Some(path) = &recording_path {
+            let seed = data.world.read_resource::<GameRng>().seed();
+            let mut recording = data.world.write_resource::<Recording>();
+            recording.seed = Some(seed);
+            match recording.save(path) {
+                Ok(()) => println!(
+                    "### Saved {} recorded frames to {:?} ###",
+                    recording.frames.len(),
+                    path
+                ),
+                Err(e) => println!("Failed to save recording to {:?}: {}", path, e),
+            }
+        }
     }
 
     fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
-        if let Some(ref progress_counter) = self.progress_counter {
-            // Check if all data has been loaded
-            if progress_counter.is_complete() {
-                println!("### GameState progress complete ###");
-                // Get the map, which is loaded in the on_start function of load state.
-
-                let tileset = {
-                    let tileset_storage = &data.world.read_resource::<AssetStorage<Tileset>>();
-                    let tileset_handle = &self.tileset_handle.take().unwrap();
-                    tileset_storage.get(tileset_handle).unwrap().clone()
-                };
-                let sprite_sheet = tileset.load_spritesheet(data.world);
-
-                let mut map = {
-                    let map_storage = &data.world.read_resource::<AssetStorage<Map>>();
-                    let map_handle = &self.map_handle.take().unwrap();
-                    map_storage.get(map_handle).unwrap().clone()
-                };
-                map.init_floors(data.world);
-                map.render_tiles(data.world, &sprite_sheet);
-                data.world.insert(map);
-
-                let player_prefab_handle = {
-                    let prefab_list = data.world.read_resource::<PrefabList>();
-                    prefab_list.get(AssetType::Player).unwrap().clone()
-                };
-                let guns_prefab_handle = {
-                    let prefab_list = data.world.read_resource::<PrefabList>();
-                    prefab_list.get(AssetType::Guns).unwrap().clone()
-                };
-                println!("### Loading player ###");
-                load_player(data.world, player_prefab_handle, guns_prefab_handle);
-                self.progress_counter = None;
-            } else {
-                println!(
-                    "Loading: {}, Failed: {}, Finished: {}, Errors: {:?}",
-                    progress_counter.num_loading(),
-                    progress_counter.num_failed(),
-                    progress_counter.num_finished(),
-                    progress_counter.errors()
-                );
+        self.elapsed_seconds += data.world.read_resource::<Time>().delta_seconds();
+        if data.world.read_resource::<Lives>().is_game_over() {
+            println!("### Game over ###");
+            let score = data.world.read_resource::<Score>().score;
+            if data.world.read_resource::<HighScores>().qualifies(score) {
+                return Trans::Switch(Box::new(InitialsEntryState::default()));
             }
+            return Trans::Switch(Box::new(GameOverState::default()));
+        }
+        if data.world.read_resource::<LevelProgress>().is_complete() {
+            println!("### Level complete in {} seconds ###", self.elapsed_seconds);
+            data.world
+                .write_resource::<EventChannel<ScoreEvent>>()
+                .single_write(ScoreEvent::LevelCleared {
+                    seconds: self.elapsed_seconds,
+                });
+            return Trans::Switch(Box::new(LevelClearState::default()));
         }
         Trans::None
     }
@@ -0,0 +1,62 @@
+use amethyst::{ecs::Entity, prelude::*};
+
+use crate::states::{
+    message::{create_hint, create_message},
+    LoadingState, TitleState,
+};
+use controls::ControlInput;
+use level::{CurrentLevel, GameplayState};
+use player::Lives;
+use score::Score;
+
+/// Shown when the player runs out of lives, he can continue the building he was in or go back
+/// to the title screen.
+#[derive(Default)]
+pub struct GameOverState {
+    messages: Vec<Entity>,
+    // the buttons have to be let go first, the player was probably shooting when he died
+    released: bool,
+}
+
+impl SimpleState for GameOverState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        println!("### GameOverState on_start ###");
+        let world = data.world;
+        world.insert(GameplayState::Paused);
+        self.messages.push(create_message(world, "GAME OVER"));
+        self.messages
+            .push(create_hint(world, "JUMP TO CONTINUE, SHOOT FOR TITLE"));
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        for message in self.messages.drain(..) {
+            let _ = data.world.delete_entity(message);
+        }
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        let input = *data.world.read_resource::<ControlInput>();
+        if !self.released {
+            self.released = !input.jump && !input.shoot;
+            return Trans::None;
+        }
+
+        if !input.jump && !input.shoot {
+            return Trans::None;
+        }
+
+        // either way it's a new game
+        let level = *data.world.read_resource::<CurrentLevel>();
+        data.world.write_resource::<Score>().reset();
+        data.world.insert(Lives::default());
+        self.messages.clear();
+        data.world.delete_all();
+        if input.jump {
+            println!("### Continuing ###");
+            Trans::Switch(Box::new(LoadingState::new(level)))
+        } else {
+            println!("### Back to the title ###");
+            Trans::Switch(Box::new(TitleState::default()))
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use amethyst::{ecs::Entity, prelude::*, ui::UiText};
+
+use crate::states::{
+    message::{create_hint, create_message},
+    GameOverState,
+};
+use controls::ControlInput;
+use level::GameplayState;
+use score::{HighScores, Score, INITIALS_LENGTH};
+
+const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Lets the player enter his initials for the high score table, up and down pick a letter and
+/// shoot accepts it.
+#[derive(Default)]
+pub struct InitialsEntryState {
+    initials: String,
+    letter: usize,
+    last_input: ControlInput,
+    message: Option<Entity>,
+    hint: Option<Entity>,
+}
+
+impl InitialsEntryState {
+    fn hint_text(&self) -> String {
+        let mut text = self.initials.clone();
+        text.push(LETTERS[self.letter] as char);
+        while text.len() < INITIALS_LENGTH {
+            text.push('_');
+        }
+        format!("ENTER YOUR INITIALS: {}", text)
+    }
+}
+
+impl SimpleState for InitialsEntryState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        println!("### InitialsEntryState on_start ###");
+        let world = data.world;
+        world.insert(GameplayState::Paused);
+        // ignore whatever was held when the player died
+        self.last_input = *world.read_resource::<ControlInput>();
+        let score = world.read_resource::<Score>().score;
+        self.message = Some(create_message(world, &format!("NEW HIGH SCORE: {}", score)));
+        let hint_text = self.hint_text();
+        self.hint = Some(create_hint(world, &hint_text));
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        for entity in self.message.take().into_iter().chain(self.hint.take()) {
+            let _ = data.world.delete_entity(entity);
+        }
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        let input = *data.world.read_resource::<ControlInput>();
+        let last_input = self.last_input;
+        self.last_input = input;
+
+        if input.up && !last_input.up {
+            self.letter = (self.letter + 1) % LETTERS.len();
+        } else if input.down && !last_input.down {
+            self.letter = (self.letter + LETTERS.len() - 1) % LETTERS.len();
+        } else if input.shoot && !last_input.shoot {
+            self.initials.push(LETTERS[self.letter] as char);
+            self.letter = 0;
+        }
+
+        if self.initials.len() >= INITIALS_LENGTH {
+            let score = data.world.read_resource::<Score>().score;
+            data.world
+                .write_resource::<HighScores>()
+                .insert(&self.initials, score);
+            return Trans::Switch(Box::new(GameOverState::default()));
+        }
+
+        let hint_text = self.hint_text();
+        if let Some(hint) = self.hint {
+            if let Some(ui_text) = data.world.write_storage::<UiText>().get_mut(hint) {
+                ui_text.text = hint_text;
+            }
+        }
+        Trans::None
+    }
+}
@@ -0,0 +1,46 @@
+use amethyst::{core::timing::Time, ecs::Entity, prelude::*};
+
+use crate::states::{message::create_message, GameState};
+use level::{CurrentLevel, GameplayState, LevelManifest};
+
+const INTRO_SECONDS: f32 = 2.;
+
+/// Shows the name of the building before the level starts.
+#[derive(Default)]
+pub struct LevelIntroState {
+    message: Option<Entity>,
+    elapsed_seconds: f32,
+}
+
+impl SimpleState for LevelIntroState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        println!("### LevelIntroState on_start ###");
+        let world = data.world;
+        world.insert(GameplayState::Paused);
+
+        let text = {
+            let current_level = world.read_resource::<CurrentLevel>();
+            let manifest = world.read_resource::<LevelManifest>();
+            format!(
+                "BUILDING {}: {}",
+                current_level.number + 1,
+                manifest.get(current_level.index).name
+            )
+        };
+        self.message = Some(create_message(world, &text));
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        if let Some(message) = self.message.take() {
+            let _ = data.world.delete_entity(message);
+        }
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        self.elapsed_seconds += data.world.read_resource::<Time>().delta_seconds();
+        if self.elapsed_seconds < INTRO_SECONDS {
+            return Trans::None;
+        }
+        Trans::Switch(Box::new(GameState::default()))
+    }
+}
@@ -0,0 +1,189 @@
+use amethyst::{
+    assets::{AssetStorage, Handle, JsonFormat, Loader, ProgressCounter},
+    prelude::*,
+    ui::UiCreator,
+};
+
+use crate::states::LevelIntroState;
+use asset::{load_assets, load_prefab_paths, AssetType, PrefabList};
+use camera::init_camera;
+use enemy::EnemyArchetypes;
+use lamp::Darkness;
+use level::{CurrentLevel, Difficulty, GameplayState, LevelManifest, LevelProgress};
+use map::{Map, Tileset, TmxFormat, TsxFormat};
+use player::{load_player, RespawnPoints};
+
+/// Loads the assets and the map of a level, then shows its intro.
+#[derive(Default)]
+pub struct LoadingState {
+    level: CurrentLevel,
+    map_path: String,
+    progress_counter: Option<ProgressCounter>,
+    map_handle: Option<Handle<Map>>,
+    map: Option<Map>,
+    tileset_handles: Vec<Handle<Tileset>>,
+}
+
+impl LoadingState {
+    pub fn new(level: CurrentLevel) -> Self {
+        LoadingState {
+            level,
+            ..LoadingState::default()
+        }
+    }
+
+    /// The directory of the map file, which tileset paths are relative to.
+    fn map_directory(&self) -> &str {
+        self.map_path.rsplitn(2, '/').nth(1).unwrap_or("")
+    }
+
+    /// Starts loading the tilesets used by the map, their paths are relative to the map.
+    fn load_tilesets(&mut self, world: &World, map: &Map) -> ProgressCounter {
+        let mut progress_counter = ProgressCounter::new();
+        let loader = world.read_resource::<Loader>();
+        let tileset_storage = world.read_resource::<AssetStorage<Tileset>>();
+        for tileset in map.tilesets.iter() {
+            let path = format!("{}/{}", self.map_directory(), tileset.source);
+            let handle = if path.ends_with(".tsx") {
+                loader.load(path, TsxFormat, &mut progress_counter, &tileset_storage)
+            } else {
+                loader.load(path, JsonFormat, &mut progress_counter, &tileset_storage)
+            };
+            self.tileset_handles.push(handle);
+        }
+        progress_counter
+    }
+}
+
+impl SimpleState for LoadingState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        println!("### LoadingState on_start ###");
+        let world = data.world;
+        world.insert(GameplayState::Paused);
+        world.insert(self.level);
+        world.write_resource::<Difficulty>().start_level();
+        self.map_path = world
+            .read_resource::<LevelManifest>()
+            .get(self.level.index)
+            .map
+            .clone();
+
+        self.progress_counter = Some(load_assets(
+            world,
+            vec![
+                AssetType::Bullet,
+                AssetType::BulletImpact,
+                AssetType::Door,
+                AssetType::Elevator,
+                AssetType::Enemy,
+                AssetType::Guns,
+                AssetType::Lamp,
+                AssetType::Player,
+            ],
+        ));
+        let enemy_prefabs = world.read_resource::<EnemyArchetypes>().prefabs();
+        load_prefab_paths(
+            world,
+            enemy_prefabs,
+            self.progress_counter.as_mut().expect("enemy prefabs"),
+        );
+
+        let mut progress = ProgressCounter::default();
+        world.exec(|mut creator: UiCreator<'_>| {
+            creator.create("ui/fps.ron", &mut progress);
+            creator.create("ui/score.ron", &mut progress);
+        });
+
+        self.map_handle = {
+            let loader = world.read_resource::<Loader>();
+            let progress_counter = self.progress_counter.as_mut().expect("map");
+            let map_storage = &world.read_resource::<AssetStorage<Map>>();
+            let map_path = self.map_path.clone();
+            if map_path.ends_with(".tmx") {
+                Some(loader.load(map_path, TmxFormat, progress_counter, map_storage))
+            } else {
+                Some(loader.load(map_path, JsonFormat, progress_counter, map_storage))
+            }
+        };
+
+        init_camera(world);
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        let is_complete = match &self.progress_counter {
+            Some(progress_counter) => {
+                if !progress_counter.is_complete() {
+                    println!(
+                        "Loading: {}, Failed: {}, Finished: {}, Errors: {:?}",
+                        progress_counter.num_loading(),
+                        progress_counter.num_failed(),
+                        progress_counter.num_finished(),
+                        progress_counter.errors()
+                    );
+                }
+                progress_counter.is_complete()
+            }
+            None => false,
+        };
+        if !is_complete {
+            return Trans::None;
+        }
+
+        // Get the map, which is loaded in the on_start function, and then load its tilesets
+        if let Some(map_handle) = self.map_handle.take() {
+            println!("### LoadingState map loaded ###");
+            let map = {
+                let map_storage = &data.world.read_resource::<AssetStorage<Map>>();
+                map_storage.get(&map_handle).unwrap().clone()
+            };
+            self.progress_counter = Some(self.load_tilesets(data.world, &map));
+            self.map = Some(map);
+            return Trans::None;
+        }
+
+        println!("### LoadingState progress complete ###");
+        let mut map = self.map.take().unwrap();
+        let sprite_sheets: Vec<_> = self
+            .tileset_handles
+            .drain(..)
+            .zip(map.tilesets.iter())
+            .map(|(tileset_handle, tileset_ref)| {
+                let mut tileset = {
+                    let tileset_storage = &data.world.read_resource::<AssetStorage<Tileset>>();
+                    tileset_storage.get(&tileset_handle).unwrap().clone()
+                };
+                if tileset_ref.source.ends_with(".tsx") {
+                    tileset.relative_to(self.map_directory());
+                }
+                tileset.load_spritesheet(data.world)
+            })
+            .collect();
+
+        let exit_floor = data
+            .world
+            .read_resource::<LevelManifest>()
+            .get(self.level.index)
+            .exit_floor;
+        data.world
+            .insert(LevelProgress::new(map.red_door_count(), exit_floor));
+        data.world.insert(Darkness::default());
+
+        data.world.insert(RespawnPoints::default());
+        map.init_floors(data.world);
+        map.render_tiles(data.world, &sprite_sheets);
+        data.world.insert(map);
+
+        let player_prefab_handle = {
+            let prefab_list = data.world.read_resource::<PrefabList>();
+            prefab_list.get(AssetType::Player).unwrap().clone()
+        };
+        let guns_prefab_handle = {
+            let prefab_list = data.world.read_resource::<PrefabList>();
+            prefab_list.get(AssetType::Guns).unwrap().clone()
+        };
+        println!("### Loading player ###");
+        load_player(data.world, player_prefab_handle, guns_prefab_handle);
+        self.progress_counter = None;
+        Trans::Switch(Box::new(LevelIntroState::default()))
+    }
+}
@@ -0,0 +1,48 @@
+use amethyst::{
+    assets::Loader,
+    ecs::Entity,
+    prelude::*,
+    ui::{Anchor, TtfFormat, UiText, UiTransform},
+};
+
+const FONT_PATH: &str = "font/square.ttf";
+const FONT_SIZE: f32 = 24.;
+const HINT_FONT_SIZE: f32 = 16.;
+const HINT_OFFSET_Y: f32 = -50.;
+
+/// Shows `text` in the middle of the screen, delete the returned entity to hide it again.
+pub fn create_message(world: &mut World, text: &str) -> Entity {
+    create_label(world, "message_text", text, 0., FONT_SIZE)
+}
+
+/// Shows smaller `text` below the message, ie. to tell the player which keys to press.
+pub fn create_hint(world: &mut World, text: &str) -> Entity {
+    create_label(world, "hint_text", text, HINT_OFFSET_Y, HINT_FONT_SIZE)
+}
+
+fn create_label(world: &mut World, id: &str, text: &str, y: f32, font_size: f32) -> Entity {
+    let font =
+        world
+            .read_resource::<Loader>()
+            .load(FONT_PATH, TtfFormat, (), &world.read_resource());
+    let transform = UiTransform::new(
+        String::from(id),
+        Anchor::Middle,
+        Anchor::Middle,
+        0.,
+        y,
+        1.,
+        600.,
+        50.,
+    );
+    world
+        .create_entity()
+        .with(transform)
+        .with(UiText::new(
+            font,
+            String::from(text),
+            [1., 1., 1., 1.],
+            font_size,
+        ))
+        .build()
+}
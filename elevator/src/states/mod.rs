@@ -1,3 +1,16 @@
+mod clear;
 mod game;
+mod game_over;
+mod initials;
+mod intro;
+mod loading;
+mod message;
+mod title;
 
+pub use self::clear::LevelClearState;
 pub use self::game::GameState;
+pub use self::game_over::GameOverState;
+pub use self::initials::InitialsEntryState;
+pub use self::intro::LevelIntroState;
+pub use self::loading::LoadingState;
+pub use self::title::TitleState;
@@ -0,0 +1,61 @@
+use amethyst::{
+    ecs::{Read, WriteStorage},
+    prelude::*,
+    ui::{UiCreator, UiFinder, UiText},
+};
+
+use crate::states::LoadingState;
+use controls::ControlInput;
+use level::{CurrentLevel, GameplayState};
+use score::{HighScores, MAX_HIGH_SCORES};
+
+/// The title screen with the high score table, shooting starts a new game.
+#[derive(Default)]
+pub struct TitleState {
+    last_shoot: bool,
+}
+
+impl SimpleState for TitleState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        println!("### TitleState on_start ###");
+        let world = data.world;
+        world.insert(GameplayState::Paused);
+        // don't start right away when shoot is still held from the last game
+        self.last_shoot = true;
+        world.exec(|mut creator: UiCreator<'_>| creator.create("ui/title.ron", ()));
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        // the labels show up once the ui prefab is loaded
+        data.world.exec(
+            |(finder, mut ui_text, high_scores): (
+                UiFinder<'_>,
+                WriteStorage<'_, UiText>,
+                Read<'_, HighScores>,
+            )| {
+                for row in 0..MAX_HIGH_SCORES {
+                    let label = finder
+                        .find(&format!("high_score_{}", row))
+                        .and_then(|entity| ui_text.get_mut(entity));
+                    if let Some(label) = label {
+                        label.text = match high_scores.entries.get(row) {
+                            Some(entry) => {
+                                format!("{}. {}  {:>6}", row + 1, entry.initials, entry.score)
+                            }
+                            None => format!("{}. ---  {:>6}", row + 1, 0),
+                        };
+                    }
+                }
+            },
+        );
+
+        let shoot = data.world.read_resource::<ControlInput>().shoot;
+        let start = shoot && !self.last_shoot;
+        self.last_shoot = shoot;
+        if !start {
+            return Trans::None;
+        }
+        data.world.delete_all();
+        Trans::Switch(Box::new(LoadingState::new(CurrentLevel::default())))
+    }
+}
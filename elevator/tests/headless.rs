@@ -0,0 +1,128 @@
+use std::path::PathBuf;
+
+use amethyst::ecs::{Join, WorldExt};
+
+use controls::ControlInput;
+use elevator_main::headless::{Simulation, DEFAULT_TIMESTEP};
+use enemy::components::Enemy;
+use level::GameplayState;
+use person::components::Person;
+
+const MAP: &str = "tilesets/floors_1.json";
+
+fn assets_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("assets")
+}
+
+/// `frames` frames of the same input.
+fn hold(input: ControlInput, frames: usize) -> Vec<ControlInput> {
+    vec![input; frames]
+}
+
+fn walk(movement: f32) -> ControlInput {
+    ControlInput {
+        movement,
+        ..ControlInput::default()
+    }
+}
+
+/// Walks around and shoots for a while, long enough for enemies to come out of doors.
+fn busy_script() -> Vec<ControlInput> {
+    let shoot = ControlInput {
+        shoot: true,
+        ..ControlInput::default()
+    };
+    let mut script = Vec::new();
+    for _ in 0..5 {
+        script.extend(hold(walk(1.), 40));
+        script.extend(hold(shoot, 2));
+        script.extend(hold(ControlInput::default(), 20));
+        script.extend(hold(walk(-1.), 40));
+    }
+    script
+}
+
+/// Where the player and every enemy are, in the order the enemies were created.
+fn positions(simulation: &Simulation) -> Vec<(f32, f32)> {
+    let world = simulation.world();
+    let persons = world.read_storage::<Person>();
+    let enemies = world.read_storage::<Enemy>();
+    let player = simulation.player_position().unwrap();
+    let mut positions = vec![(player.x, player.y)];
+    positions.extend(
+        (&persons, &enemies)
+            .join()
+            .map(|(person, _enemy)| (person.position.x, person.position.y)),
+    );
+    positions
+}
+
+#[test]
+fn player_walks_where_the_script_says() {
+    // give him time to land on the top floor first
+    let mut script = hold(ControlInput::default(), 30);
+    script.extend(hold(walk(1.), 30));
+    script.extend(hold(walk(-1.), 15));
+    let mut simulation = Simulation::new(&assets_dir(), MAP, script, DEFAULT_TIMESTEP, 0).unwrap();
+
+    simulation.run(30);
+    let start = simulation.player_position().unwrap();
+    simulation.run(30);
+    let walked = simulation.player_position().unwrap();
+    assert!(walked.x > start.x + 5., "{:?} -> {:?}", start, walked);
+    assert!(
+        (walked.y - start.y).abs() < 1.,
+        "{:?} -> {:?}",
+        start,
+        walked
+    );
+
+    simulation.run(15);
+    let back = simulation.player_position().unwrap();
+    assert!(back.x < walked.x, "{:?} -> {:?}", walked, back);
+}
+
+#[test]
+fn same_seed_gives_the_same_world() {
+    let run = |seed| {
+        let mut simulation =
+            Simulation::new(&assets_dir(), MAP, busy_script(), DEFAULT_TIMESTEP, seed).unwrap();
+        simulation.run(busy_script().len() as u64);
+        positions(&simulation)
+    };
+    assert_eq!(run(7), run(7));
+}
+
+#[test]
+fn replay_gives_the_same_world_as_the_recorded_run() {
+    let script = busy_script();
+    let mut recorded =
+        Simulation::new(&assets_dir(), MAP, script.clone(), DEFAULT_TIMESTEP, 7).unwrap();
+    recorded.run(script.len() as u64);
+    let mut replayed =
+        Simulation::from_recording(&assets_dir(), MAP, &recorded.recording()).unwrap();
+    assert_eq!(replayed.recorded_frames(), script.len());
+    replayed.run(script.len() as u64);
+    assert_eq!(positions(&recorded), positions(&replayed));
+}
+
+#[test]
+fn replay_starts_at_the_time_the_recording_did() {
+    let script = busy_script();
+    let mut recorded =
+        Simulation::new(&assets_dir(), MAP, script.clone(), DEFAULT_TIMESTEP, 7).unwrap();
+    // like a session that was recorded after a while on the title screen
+    recorded.world_mut().insert(GameplayState::Paused);
+    recorded.run(300);
+    recorded.world_mut().insert(GameplayState::Running);
+    recorded.run(script.len() as u64);
+    let recording = recorded.recording();
+    assert_eq!(recording.frames.len(), script.len());
+    assert!(recording.frames[0].paused_seconds > 4.9);
+
+    let mut replayed = Simulation::from_recording(&assets_dir(), MAP, &recording).unwrap();
+    replayed.run(script.len() as u64);
+    assert_eq!(positions(&recorded), positions(&replayed));
+}
@@ -28,6 +28,8 @@ pub enum AnimationId {
     // gun
     PersonShoot,
     PersonJumpShoot,
+    MachineGunShoot,
+    ShotgunShoot,
     Holster, // hidden
     // doors
     DoorEntry,
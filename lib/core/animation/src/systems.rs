@@ -35,6 +35,7 @@ impl<'s> System<'s> for AnimationControlSystem {
                     if !animation_control_set.has_animation(animation_id) {
                         let end = match animation_id {
                             AnimationId::PersonShoot
+                            | AnimationId::ShotgunShoot
                             | AnimationId::Idle
                             | AnimationId::BulletImpact
                             | AnimationId::Die => EndControl::Stay,
@@ -20,6 +20,7 @@ pub enum AssetType {
     Door,
     Elevator,
     Enemy,
+    Lamp,
     Player,
     Guns,
 }
@@ -42,6 +43,8 @@ impl SpriteSheetList {
 #[derive(Default)]
 pub struct PrefabList {
     prefabs: HashMap<AssetType, Handle<Prefab<AnimationPrefabData>>>,
+    /// prefabs named in data files (like the enemy archetypes), by path
+    prefabs_by_path: HashMap<String, Handle<Prefab<AnimationPrefabData>>>,
 }
 
 impl PrefabList {
@@ -56,6 +59,10 @@ impl PrefabList {
     pub fn get(&self, asset_type: AssetType) -> Option<&Handle<Prefab<AnimationPrefabData>>> {
         self.prefabs.get(&asset_type)
     }
+
+    pub fn get_path(&self, ron_path: &str) -> Option<&Handle<Prefab<AnimationPrefabData>>> {
+        self.prefabs_by_path.get(ron_path)
+    }
 }
 
 /// Loads `SpriteSheetHandle`s for all the assets in the `AssetType` list into the `world`
@@ -77,12 +84,13 @@ pub fn load_assets(world: &mut World, asset_type_list: Vec<AssetType>) -> Progre
             AssetType::Elevator => ("texture/elevator.png", "prefabs/elevator.ron"),
             AssetType::Enemy => ("texture/enemy.png", "prefabs/enemy.ron"),
             AssetType::Guns => ("texture/guns.png", "prefabs/guns.ron"),
+            AssetType::Lamp => ("texture/lamp.png", "prefabs/lamp.ron"),
             AssetType::Player => ("texture/player.png", "prefabs/player.ron"),
         };
 
         match asset_type {
             // without animation
-            AssetType::Bullet | AssetType::Elevator => {
+            AssetType::Bullet | AssetType::Elevator | AssetType::Lamp => {
                 let sprite_sheet_handle =
                     get_sprite_sheet_handle(world, texture_path, ron_path, &mut progress_counter);
                 sprite_sheet_list.insert(asset_type, sprite_sheet_handle);
@@ -104,6 +112,21 @@ pub fn load_assets(world: &mut World, asset_type_list: Vec<AssetType>) -> Progre
     progress_counter
 }
 
+/// Loads extra animation prefabs by path into the `PrefabList`, after `load_assets`.
+pub fn load_prefab_paths(
+    world: &mut World,
+    ron_paths: Vec<String>,
+    progress_counter: &mut ProgressCounter,
+) {
+    for ron_path in ron_paths {
+        let prefab_handle = get_animation_prefab_handle(world, &ron_path, progress_counter);
+        world
+            .write_resource::<PrefabList>()
+            .prefabs_by_path
+            .insert(ron_path, prefab_handle);
+    }
+}
+
 /// Returns a `SpriteSheetHandle` for the given texture and ron files.
 pub fn get_sprite_sheet_handle(
     world: &World,
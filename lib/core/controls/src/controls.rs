@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// The values of the "move" axis and the "up", "down", "jump" and "shoot" actions
+/// from `config/bindings.ron` for the current frame.
+///
+/// Gameplay systems read this resource instead of the `InputHandler` so that the
+/// input can come from the keyboard or from a script (ie. when running headless).
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ControlInput {
+    pub movement: f32,
+    pub up: bool,
+    pub down: bool,
+    pub jump: bool,
+    pub shoot: bool,
+}
@@ -0,0 +1,6 @@
+mod controls;
+mod recording;
+
+pub mod systems;
+pub use self::controls::ControlInput;
+pub use self::recording::{RecordedFrame, Recording};
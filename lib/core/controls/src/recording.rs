@@ -0,0 +1,41 @@
+use std::{fs, fs::File, path::Path};
+
+use amethyst::error::Error;
+use serde::{Deserialize, Serialize};
+
+use crate::ControlInput;
+
+/// The input and the length of a single recorded frame.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct RecordedFrame {
+    pub delta_seconds: f32,
+    /// seconds that passed with the game paused (ie. in the menus) since the frame before
+    #[serde(default)]
+    pub paused_seconds: f64,
+    pub input: ControlInput,
+}
+
+/// A whole play session that can be saved, attached to a bug report and replayed.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Recording {
+    /// seed used for gameplay randomness, if the session had one
+    pub seed: Option<u64>,
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl Recording {
+    pub fn load(path: &Path) -> Result<Recording, Error> {
+        let file = File::open(path)?;
+        Ok(ron::de::from_reader(file)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        fs::write(path, ron::ser::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// The recorded controls, one per frame.
+    pub fn inputs(&self) -> Vec<ControlInput> {
+        self.frames.iter().map(|frame| frame.input).collect()
+    }
+}
@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use amethyst::core::timing::Time;
+use amethyst::core::SystemDesc;
+use amethyst::derive::SystemDesc;
+use amethyst::ecs::{Read, System, SystemData, World, Write};
+use amethyst::input::{InputHandler, StringBindings};
+
+use crate::{ControlInput, RecordedFrame, Recording};
+
+/// Copies the keyboard bindings into the `ControlInput` resource every frame.
+#[derive(SystemDesc)]
+pub struct InputControlsSystem;
+
+impl<'s> System<'s> for InputControlsSystem {
+    type SystemData = (
+        Read<'s, InputHandler<StringBindings>>,
+        Write<'s, ControlInput>,
+    );
+
+    fn run(&mut self, (input, mut control_input): Self::SystemData) {
+        *control_input = ControlInput {
+            movement: input.axis_value("move").expect("Move action exists"),
+            up: input.action_is_down("up").expect("Up action exists"),
+            down: input.action_is_down("down").expect("Down action exists"),
+            jump: input.action_is_down("jump").expect("Jump action exists"),
+            shoot: input.action_is_down("shoot").expect("Shoot action exists"),
+        };
+    }
+}
+
+/// Feeds a fixed list of per-frame inputs into the `ControlInput` resource.
+/// Once the script runs out the controls are released.
+#[derive(Default)]
+pub struct ScriptedControlsSystem {
+    script: Vec<ControlInput>,
+    frame: usize,
+}
+
+impl ScriptedControlsSystem {
+    pub fn new(script: Vec<ControlInput>) -> Self {
+        ScriptedControlsSystem { script, frame: 0 }
+    }
+}
+
+impl<'s> System<'s> for ScriptedControlsSystem {
+    type SystemData = Write<'s, ControlInput>;
+
+    fn run(&mut self, mut control_input: Self::SystemData) {
+        *control_input = self.script.get(self.frame).copied().unwrap_or_default();
+        self.frame += 1;
+    }
+}
+
+/// Drives `Time` from the recorded length of every frame while a recording is replayed.
+///
+/// The frames are counted on a clock of its own that replaces `Time` every frame, so the
+/// time that passes while the game loop itself runs doesn't leak into the replay. The clock
+/// skips ahead by the time the recorded session spent paused before each frame, so it shows
+/// the same absolute time the recorded frame did. Once the recording runs out the frames are
+/// `fallback_seconds` long.
+pub struct ReplayTimeSystem {
+    frames: Vec<RecordedFrame>,
+    fallback_seconds: f32,
+    frame: usize,
+    clock: Time,
+}
+
+impl ReplayTimeSystem {
+    pub fn new(frames: Vec<RecordedFrame>, fallback_seconds: f32) -> Self {
+        ReplayTimeSystem {
+            frames,
+            fallback_seconds,
+            frame: 0,
+            clock: Time::default(),
+        }
+    }
+}
+
+impl<'s> System<'s> for ReplayTimeSystem {
+    type SystemData = Write<'s, Time>;
+
+    fn run(&mut self, mut time: Self::SystemData) {
+        match self.frames.get(self.frame) {
+            Some(frame) => {
+                if frame.paused_seconds > 0. {
+                    self.clock
+                        .set_delta_time(Duration::from_secs_f64(frame.paused_seconds));
+                }
+                self.clock.set_delta_seconds(frame.delta_seconds);
+            }
+            None => self.clock.set_delta_seconds(self.fallback_seconds),
+        }
+        self.clock.increment_frame_number();
+        time.clone_from(&self.clock);
+        self.frame += 1;
+    }
+}
+
+/// Appends the `ControlInput` of every frame to the `Recording` resource, along with the
+/// time the game spent paused since the last recorded frame.
+#[derive(Default)]
+pub struct ControlRecorderSystem {
+    /// the absolute time at the end of the last recorded frame
+    last_frame_end: Duration,
+}
+
+impl<'s> System<'s> for ControlRecorderSystem {
+    type SystemData = (Read<'s, Time>, Read<'s, ControlInput>, Write<'s, Recording>);
+
+    fn run(&mut self, (time, control_input, mut recording): Self::SystemData) {
+        let frame_start = time
+            .absolute_time()
+            .checked_sub(time.delta_time())
+            .unwrap_or_default();
+        let paused = frame_start
+            .checked_sub(self.last_frame_end)
+            .unwrap_or_default();
+        self.last_frame_end = time.absolute_time();
+        recording.frames.push(RecordedFrame {
+            delta_seconds: time.delta_seconds(),
+            paused_seconds: paused.as_secs_f64(),
+            input: *control_input,
+        });
+    }
+}
@@ -23,18 +23,15 @@ impl<'a> System<'a> for UiFpsSystem {
     );
 
     fn run(&mut self, (time, mut ui_text, fps_counter, finder): Self::SystemData) {
-        if self.fps_display.is_none() {
-            if let Some(fps_entity) = finder.find("fps_text") {
-                self.fps_display = Some(fps_entity);
-            }
-        }
-        if let Some(fps_entity) = self.fps_display {
-            if let Some(fps_display) = ui_text.get_mut(fps_entity) {
+        // the label is created again with every level, so look it up until it is found
+        match self.fps_display.and_then(|entity| ui_text.get_mut(entity)) {
+            Some(fps_display) => {
                 if time.frame_number() % 20 == 0 {
                     let fps = fps_counter.sampled_fps();
                     fps_display.text = format!("FPS: {:.*}", 2, fps);
                 }
             }
+            None => self.fps_display = finder.find("fps_text"),
         }
     }
 }
@@ -0,0 +1,356 @@
+use std::{collections::BTreeMap, fs::File, path::Path};
+
+use amethyst::error::Error;
+use serde::{Deserialize, Serialize};
+
+/// The tuning of one difficulty preset, before any ramping up or easing off.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DifficultySettings {
+    /// enemies alive at the same time
+    pub max_enemies: usize,
+    /// chance (each frame) of an enemy coming out of a door
+    pub enemy_factor: f32,
+    pub time_between_spawns: f64,
+    /// chance of an enemy going after the player when they're on the same floor
+    pub pursuit_factor_same_floor: f32,
+    /// enemy shots added to the fire budget each second, and how many can be saved up
+    pub fire_rate: f32,
+    pub max_shots: f32,
+    pub elevator_velocity: f32,
+    pub elevator_wait_time: f64,
+}
+
+impl Default for DifficultySettings {
+    fn default() -> Self {
+        DifficultySettings {
+            max_enemies: 3,
+            enemy_factor: 0.001,
+            time_between_spawns: 3.0,
+            pursuit_factor_same_floor: 0.05,
+            fire_rate: 0.6,
+            max_shots: 2.,
+            elevator_velocity: 20.0,
+            elevator_wait_time: 2.2,
+        }
+    }
+}
+
+/// How the game gets harder the deeper the player gets and the longer a level takes.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct DifficultyRamp {
+    pub per_floor: f32,
+    pub per_minute: f32,
+    /// the most the settings are scaled by
+    pub max_scale: f32,
+}
+
+/// How the game eases off when the player keeps dying.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct AdaptiveDifficulty {
+    pub ease_per_death: f32,
+    /// the least the settings are scaled by
+    pub min_scale: f32,
+    /// one death is forgiven every time the player survives this long
+    pub recovery_seconds: f32,
+}
+
+/// The difficulty presets and how they change during a level, loaded from
+/// `config/difficulty.ron`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct DifficultyConfig {
+    /// the preset used when none is picked on the command line
+    pub preset: String,
+    pub presets: BTreeMap<String, DifficultySettings>,
+    pub ramp: DifficultyRamp,
+    #[serde(default)]
+    pub adaptive: Option<AdaptiveDifficulty>,
+}
+
+impl DifficultyConfig {
+    pub fn load(path: &Path) -> Result<DifficultyConfig, Error> {
+        let file = File::open(path)?;
+        let config: DifficultyConfig = ron::de::from_reader(file)?;
+        if !config.presets.contains_key(&config.preset) {
+            return Err(Error::from_string(format!(
+                "Unknown difficulty {} in {:?}",
+                config.preset, path
+            )));
+        }
+        for (name, settings) in config.presets.iter() {
+            // the spawn and pursuit chances are drawn from one over the factors
+            let positive = [
+                ("enemy_factor", settings.enemy_factor as f64),
+                ("time_between_spawns", settings.time_between_spawns),
+                (
+                    "pursuit_factor_same_floor",
+                    settings.pursuit_factor_same_floor as f64,
+                ),
+                ("elevator_velocity", settings.elevator_velocity as f64),
+            ];
+            for (field, value) in positive.iter() {
+                if value.is_nan() || *value <= 0. {
+                    return Err(Error::from_string(format!(
+                        "{} of difficulty {} in {:?} has to be above zero",
+                        field, name, path
+                    )));
+                }
+            }
+        }
+        let ramp = &config.ramp;
+        if ramp.per_floor < 0. || ramp.per_minute < 0. || ramp.max_scale <= 0. {
+            return Err(Error::from_string(format!(
+                "The difficulty ramp in {:?} has to go up",
+                path
+            )));
+        }
+        if let Some(adaptive) = &config.adaptive {
+            if adaptive.ease_per_death < 0. || adaptive.min_scale <= 0. {
+                return Err(Error::from_string(format!(
+                    "The adaptive difficulty in {:?} has to stay above zero",
+                    path
+                )));
+            }
+        }
+        Ok(config)
+    }
+
+    /// The difficulty for a preset, the default preset when `preset` is `None`.
+    pub fn difficulty(&self, preset: Option<&str>) -> Result<Difficulty, Error> {
+        let name = preset.unwrap_or(&self.preset);
+        match self.presets.get(name) {
+            Some(settings) => Ok(Difficulty {
+                name: name.to_string(),
+                settings: settings.clone(),
+                ramp: self.ramp.clone(),
+                adaptive: self.adaptive.clone(),
+                ..Difficulty::default()
+            }),
+            None => Err(Error::from_string(format!("Unknown difficulty {}", name))),
+        }
+    }
+}
+
+/// The difficulty of the game being played, read by the spawning, enemy AI and elevator
+/// systems.
+#[derive(Clone, Debug, Default)]
+pub struct Difficulty {
+    pub name: String,
+    pub settings: DifficultySettings,
+    pub ramp: DifficultyRamp,
+    pub adaptive: Option<AdaptiveDifficulty>,
+    /// how many floors below the top the player is
+    pub depth: usize,
+    pub level_seconds: f32,
+    /// deaths not forgiven yet
+    pub recent_deaths: usize,
+    pub seconds_since_death: f32,
+}
+
+impl Difficulty {
+    /// Starts over ramping up for a new level, the deaths are kept.
+    pub fn start_level(&mut self) {
+        self.depth = 0;
+        self.level_seconds = 0.;
+    }
+
+    pub fn record_death(&mut self) {
+        self.recent_deaths += 1;
+        self.seconds_since_death = 0.;
+    }
+
+    /// Moves the clock on, forgiving a death when the player survived long enough.
+    pub fn update(&mut self, seconds: f32, depth: usize) {
+        self.depth = depth;
+        self.level_seconds += seconds;
+        self.seconds_since_death += seconds;
+        if let Some(adaptive) = &self.adaptive {
+            if self.recent_deaths > 0 && self.seconds_since_death >= adaptive.recovery_seconds {
+                self.recent_deaths -= 1;
+                self.seconds_since_death = 0.;
+            }
+        }
+    }
+
+    /// What the settings are scaled by right now, above 1 is harder.
+    pub fn scale(&self) -> f32 {
+        let ramp = (1.
+            + self.ramp.per_floor * self.depth as f32
+            + self.ramp.per_minute * self.level_seconds / 60.)
+            .min(self.ramp.max_scale.max(1.));
+        match &self.adaptive {
+            Some(adaptive) => {
+                let ease = 1. - adaptive.ease_per_death * self.recent_deaths as f32;
+                (ramp * ease).max(adaptive.min_scale)
+            }
+            None => ramp,
+        }
+    }
+
+    pub fn max_enemies(&self) -> usize {
+        ((self.settings.max_enemies as f32 * self.scale()).round() as usize).max(1)
+    }
+
+    pub fn enemy_factor(&self) -> f32 {
+        (self.settings.enemy_factor * self.scale()).min(1.)
+    }
+
+    pub fn time_between_spawns(&self) -> f64 {
+        self.settings.time_between_spawns / self.scale() as f64
+    }
+
+    pub fn pursuit_factor_same_floor(&self) -> f32 {
+        (self.settings.pursuit_factor_same_floor * self.scale()).min(1.)
+    }
+
+    pub fn fire_rate(&self) -> f32 {
+        self.settings.fire_rate * self.scale()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-5,
+            "{} is not {}",
+            actual,
+            expected
+        );
+    }
+
+    fn ramping() -> Difficulty {
+        Difficulty {
+            ramp: DifficultyRamp {
+                per_floor: 0.1,
+                per_minute: 0.2,
+                max_scale: 1.5,
+            },
+            ..Difficulty::default()
+        }
+    }
+
+    fn easing() -> Difficulty {
+        Difficulty {
+            adaptive: Some(AdaptiveDifficulty {
+                ease_per_death: 0.2,
+                min_scale: 0.5,
+                recovery_seconds: 60.,
+            }),
+            ..ramping()
+        }
+    }
+
+    fn config() -> DifficultyConfig {
+        let mut presets = BTreeMap::new();
+        presets.insert(String::from("normal"), DifficultySettings::default());
+        DifficultyConfig {
+            preset: String::from("normal"),
+            presets,
+            ramp: ramping().ramp,
+            adaptive: easing().adaptive,
+        }
+    }
+
+    fn load(name: &str, config: &DifficultyConfig) -> Result<DifficultyConfig, Error> {
+        let path = std::env::temp_dir().join(format!(
+            "difficulty_test_{}_{}.ron",
+            name,
+            std::process::id()
+        ));
+        fs::write(&path, ron::ser::to_string(config).unwrap()).unwrap();
+        let loaded = DifficultyConfig::load(&path);
+        let _ = fs::remove_file(&path);
+        loaded
+    }
+
+    #[test]
+    fn ramps_up_with_depth_and_time_up_to_the_most() {
+        let mut difficulty = ramping();
+        assert_close(difficulty.scale(), 1.);
+        assert_eq!(difficulty.max_enemies(), 3);
+
+        difficulty.update(60., 2);
+        assert_close(difficulty.scale(), 1.4);
+        assert_eq!(difficulty.max_enemies(), 4);
+        assert!((difficulty.time_between_spawns() - 3. / 1.4).abs() < 1e-5);
+        assert_close(difficulty.pursuit_factor_same_floor(), 0.07);
+
+        difficulty.update(120., 2);
+        assert_close(difficulty.scale(), 1.5);
+        assert_eq!(difficulty.max_enemies(), 5);
+        assert!((difficulty.time_between_spawns() - 2.).abs() < 1e-5);
+        assert_close(difficulty.pursuit_factor_same_floor(), 0.075);
+
+        difficulty.start_level();
+        assert_close(difficulty.scale(), 1.);
+    }
+
+    #[test]
+    fn eases_off_for_every_death_down_to_the_least() {
+        let mut difficulty = easing();
+        difficulty.record_death();
+        assert_close(difficulty.scale(), 0.8);
+        difficulty.update(30., 2);
+        difficulty.record_death();
+        assert_close(difficulty.scale(), 1.3 * 0.6);
+
+        difficulty.start_level();
+        difficulty.record_death();
+        assert_close(difficulty.scale(), 0.5);
+        assert_eq!(difficulty.max_enemies(), 2);
+        assert!((difficulty.time_between_spawns() - 6.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn forgives_a_death_after_surviving_long_enough() {
+        let mut difficulty = easing();
+        difficulty.record_death();
+        difficulty.record_death();
+        difficulty.update(30., 0);
+        assert_eq!(difficulty.recent_deaths, 2);
+        difficulty.update(30., 0);
+        assert_eq!(difficulty.recent_deaths, 1);
+        assert_close(difficulty.seconds_since_death, 0.);
+
+        difficulty.update(59., 1);
+        assert_eq!(difficulty.recent_deaths, 1);
+        difficulty.update(1., 1);
+        assert_eq!(difficulty.recent_deaths, 0);
+        assert_eq!(difficulty.depth, 1);
+
+        difficulty.update(600., 1);
+        assert_eq!(difficulty.recent_deaths, 0);
+    }
+
+    #[test]
+    fn loads_only_factors_above_zero() {
+        assert_eq!(load("valid", &config()).unwrap(), config());
+
+        let mut unknown = config();
+        unknown.preset = String::from("nightmare");
+        assert!(load("unknown", &unknown).is_err());
+
+        let mut zero = config();
+        zero.presets.get_mut("normal").unwrap().enemy_factor = 0.;
+        assert!(load("zero", &zero).is_err());
+
+        let mut negative = config();
+        negative
+            .presets
+            .get_mut("normal")
+            .unwrap()
+            .time_between_spawns = -1.;
+        assert!(load("negative", &negative).is_err());
+
+        let mut flat = config();
+        flat.ramp.max_scale = 0.;
+        assert!(load("flat", &flat).is_err());
+
+        let mut vanishing = config();
+        vanishing.adaptive.as_mut().unwrap().min_scale = -0.5;
+        assert!(load("vanishing", &vanishing).is_err());
+    }
+}
@@ -0,0 +1,9 @@
+mod difficulty;
+mod manifest;
+mod progress;
+
+pub use self::difficulty::{
+    AdaptiveDifficulty, Difficulty, DifficultyConfig, DifficultyRamp, DifficultySettings,
+};
+pub use self::manifest::{LevelDescription, LevelManifest};
+pub use self::progress::{CurrentLevel, GameplayState, LevelProgress};
@@ -0,0 +1,38 @@
+use std::{fs::File, path::Path};
+
+use amethyst::error::Error;
+use serde::{Deserialize, Serialize};
+
+/// A single building of the game.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct LevelDescription {
+    /// shown on the level intro screen
+    pub name: String,
+    /// path of the map, relative to the assets directory
+    pub map: String,
+    /// floor of the basement exit, where the player escapes once all documents are collected
+    #[serde(default)]
+    pub exit_floor: usize,
+}
+
+/// The buildings of the game, in the order they are played.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct LevelManifest {
+    pub levels: Vec<LevelDescription>,
+}
+
+impl LevelManifest {
+    pub fn load(path: &Path) -> Result<LevelManifest, Error> {
+        let file = File::open(path)?;
+        let manifest: LevelManifest = ron::de::from_reader(file)?;
+        if manifest.levels.is_empty() {
+            return Err(Error::from_string(format!("No levels in {:?}", path)));
+        }
+        Ok(manifest)
+    }
+
+    /// The level at `index`, the game starts over from the first building after the last one.
+    pub fn get(&self, index: usize) -> &LevelDescription {
+        &self.levels[index % self.levels.len()]
+    }
+}
@@ -0,0 +1,97 @@
+/// Whether the gameplay systems are running, they are paused during intro and clear screens.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GameplayState {
+    Running,
+    Paused,
+}
+
+impl Default for GameplayState {
+    fn default() -> Self {
+        GameplayState::Running
+    }
+}
+
+/// The level being played, `number` keeps counting when the buildings start over.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CurrentLevel {
+    pub index: usize,
+    pub number: usize,
+}
+
+impl CurrentLevel {
+    pub fn next(&self) -> CurrentLevel {
+        CurrentLevel {
+            index: self.index + 1,
+            number: self.number + 1,
+        }
+    }
+}
+
+/// What the player has done towards finishing the current level.
+#[derive(Debug, Default)]
+pub struct LevelProgress {
+    pub documents_total: usize,
+    /// ids of the red doors the documents were collected from
+    pub collected_documents: Vec<usize>,
+    pub exit_floor: usize,
+    pub reached_exit: bool,
+    /// ids of the lamps that were shot down, they aren't hung up again
+    pub broken_lamps: Vec<usize>,
+    /// ids of the blue doors the weapons were picked up from
+    pub collected_pickups: Vec<usize>,
+}
+
+impl LevelProgress {
+    pub fn new(documents_total: usize, exit_floor: usize) -> Self {
+        LevelProgress {
+            documents_total,
+            exit_floor,
+            ..LevelProgress::default()
+        }
+    }
+
+    /// Collects the documents behind a red door, returns false when they were already taken.
+    pub fn collect_document(&mut self, door_id: usize) -> bool {
+        if self.collected_documents.contains(&door_id) {
+            return false;
+        }
+        self.collected_documents.push(door_id);
+        println!(
+            "### Collected documents {}/{} ###",
+            self.collected_documents.len(),
+            self.documents_total
+        );
+        true
+    }
+
+    pub fn break_lamp(&mut self, lamp_id: usize) {
+        if !self.broken_lamps.contains(&lamp_id) {
+            self.broken_lamps.push(lamp_id);
+        }
+    }
+
+    /// Picks up the weapon behind a blue door, returns false when it was already taken.
+    pub fn collect_pickup(&mut self, door_id: usize) -> bool {
+        if self.collected_pickups.contains(&door_id) {
+            return false;
+        }
+        self.collected_pickups.push(door_id);
+        true
+    }
+
+    /// Ids of the doors whose rooms have been visited.
+    pub fn used_doors(&self) -> Vec<usize> {
+        let mut used_doors = self.collected_documents.clone();
+        used_doors.extend(self.collected_pickups.iter());
+        used_doors
+    }
+
+    pub fn has_all_documents(&self) -> bool {
+        self.collected_documents.len() >= self.documents_total
+    }
+
+    /// The level is cleared when every document is collected and the player is at the exit.
+    pub fn is_complete(&self) -> bool {
+        self.has_all_documents() && self.reached_exit
+    }
+}
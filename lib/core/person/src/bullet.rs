@@ -4,14 +4,15 @@ use amethyst::{
         math::{Vector2, Vector3},
         Named, Transform,
     },
-    ecs::{Entities, Entity, LazyUpdate, ReadExpect},
+    ecs::{Entities, Entity, LazyUpdate, ReadExpect, WriteStorage},
     renderer::{sprite::SpriteSheetHandle, transparent::Transparent, SpriteRender},
 };
 
-use crate::components::{Bullet, BulletImpact};
+use crate::components::{Bullet, BulletImpact, BulletLifetime, Gun};
+use crate::weapon::Weapon;
 use animation::components::{Animation, AnimationId, AnimationPrefabData};
 use physics::components::{
-    Collidee, Collider, DefaultTransformation, Direction, Directions, Motion,
+    Collidee, Collider, DefaultTransformation, Direction, Directions, Motion, Proximity,
 };
 
 const SCALE: f32 = 1.0;
@@ -19,17 +20,33 @@ const OFFSET_X: f32 = 8.;
 const OFFSET_Y: f32 = 3.;
 const BULLET_WIDTH: f32 = 6.;
 const BULLET_HEIGHT: f32 = 3.;
-const BULLET_VELOCITY: f32 = 200.0;
+// how close a bullet gets before whoever it's heading for notices it
+const BULLET_WARNING_DISTANCE: f32 = 48.0;
+
+/// Where a bullet starts out and which way it goes.
+pub struct BulletSpawn<'a> {
+    /// the middle of the shooter, the bullet comes out in front of him
+    pub position: Vector2<f32>,
+    pub weapon: &'a Weapon,
+    /// degrees above straight ahead
+    pub angle: f32,
+    /// the way the shooter is facing
+    pub direction: &'a Direction,
+}
 
 pub fn spawn_bullet(
     entities: &Entities,
     gun_entity: Entity,
     sprite_sheet_handle: SpriteSheetHandle,
-    shoot_start_position_x: f32,
-    shoot_start_position_y: f32,
-    shooter_direction: &Direction,
+    spawn: BulletSpawn,
     lazy_update: &ReadExpect<LazyUpdate>,
 ) {
+    let BulletSpawn {
+        position,
+        weapon,
+        angle,
+        direction: shooter_direction,
+    } = spawn;
     let bullet_entity: Entity = entities.create();
     let scale = SCALE;
 
@@ -40,7 +57,9 @@ pub fn spawn_bullet(
         sprite_sheet: sprite_sheet_handle,
         sprite_number: 0,
     };
+    let velocity = weapon.bullet_velocity;
     let mut motion = Motion::new();
+    motion.velocity.y = velocity * angle.to_radians().tan();
 
     let mut direction = Direction::new(
         Directions::Right,
@@ -51,31 +70,37 @@ pub fn spawn_bullet(
 
     let bullet_start_position: f32 = match shooter_direction.x {
         Directions::Right => {
-            motion.velocity.x = BULLET_VELOCITY;
+            motion.velocity.x = velocity;
             direction.x = Directions::Right;
-            shoot_start_position_x + OFFSET_X
+            position.x + OFFSET_X
         }
         Directions::Left => {
-            motion.velocity.x = -BULLET_VELOCITY;
+            motion.velocity.x = -velocity;
             direction.x = Directions::Left;
-            shoot_start_position_x - OFFSET_X
+            position.x - OFFSET_X
         }
         _ => 0.,
     };
 
     let mut collider = Collider::new(BULLET_WIDTH * scale, BULLET_HEIGHT * scale); // bullet width and height
+    collider.owner = Some(gun_entity);
     let bbox = &mut collider.bounding_box;
-    bbox.position = Vector2::new(bullet_start_position, shoot_start_position_y + OFFSET_Y);
+    bbox.position = Vector2::new(bullet_start_position, position.y + OFFSET_Y);
     bbox.old_position = bbox.position;
 
     transform.set_translation_x(bullet_start_position);
-    transform.set_translation_y(shoot_start_position_y + OFFSET_Y);
+    transform.set_translation_y(position.y + OFFSET_Y);
     transform.set_translation_z(1.0);
 
     lazy_update.insert(bullet_entity, Bullet::new(Some(gun_entity)));
+    lazy_update.insert(
+        bullet_entity,
+        BulletLifetime::new(weapon.lifetime, weapon.range),
+    );
     lazy_update.insert(bullet_entity, Named::new("Bullet"));
     lazy_update.insert(bullet_entity, collider);
     lazy_update.insert(bullet_entity, Collidee::default());
+    lazy_update.insert(bullet_entity, Proximity::new(BULLET_WARNING_DISTANCE));
     lazy_update.insert(bullet_entity, DefaultTransformation::default());
     lazy_update.insert(bullet_entity, sprite_render);
     lazy_update.insert(bullet_entity, motion);
@@ -84,6 +109,23 @@ pub fn spawn_bullet(
     lazy_update.insert(bullet_entity, Transparent);
 }
 
+/// Removes a bullet and gives its shot back to the gun that fired it, however the bullet ended.
+pub fn despawn_bullet(
+    entities: &Entities,
+    guns: &mut WriteStorage<Gun>,
+    bullet_entity: Entity,
+    bullet: &mut Bullet,
+) {
+    if bullet.spent {
+        return;
+    }
+    bullet.spent = true;
+    if let Some(gun) = bullet.parent.and_then(|parent| guns.get_mut(parent)) {
+        gun.shots_fired -= 1;
+    }
+    let _ = entities.delete(bullet_entity);
+}
+
 pub fn show_bullet_impact(
     entities: &Entities,
     prefab_handle: Handle<Prefab<AnimationPrefabData>>,
@@ -8,10 +8,32 @@ pub struct BulletImpact;
 #[storage(DenseVecStorage)]
 pub struct Bullet {
     pub parent: Option<Entity>,
+    /// it's been despawned and its shot given back to the gun, it's gone at the end of the frame
+    pub spent: bool,
 }
 
 impl Bullet {
     pub fn new(parent: Option<Entity>) -> Self {
-        Bullet { parent }
+        Bullet {
+            parent,
+            spent: false,
+        }
+    }
+}
+
+/// How much longer and how much further a bullet keeps flying before it's gone.
+#[derive(Component)]
+#[storage(DenseVecStorage)]
+pub struct BulletLifetime {
+    pub seconds_left: f32,
+    pub range_left: f32,
+}
+
+impl BulletLifetime {
+    pub fn new(seconds: f32, range: f32) -> Self {
+        BulletLifetime {
+            seconds_left: seconds,
+            range_left: range,
+        }
     }
 }
@@ -1,5 +1,7 @@
 use amethyst::ecs::{Component, DenseVecStorage};
 
+use crate::weapon::Weapon;
+
 #[allow(dead_code)] // remove when all variants are finished
 #[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
 pub enum GunState {
@@ -17,6 +19,11 @@ pub struct Gun {
     pub last_shoot_state: bool,
     pub last_shot_seconds: f64,
     pub spawned_bullet: bool,
+    pub weapon: Weapon,
+    /// shots left in the magazine, `None` when the weapon never runs out
+    pub ammo: Option<u32>,
+    /// how far above the middle of the shooter the next bullet goes, to miss on purpose
+    pub aim_offset: f32,
 }
 
 impl Gun {
@@ -28,6 +35,34 @@ impl Gun {
             last_shoot_state: false,
             last_shot_seconds: -1.0,
             spawned_bullet: false,
+            weapon: Weapon::default(),
+            ammo: None,
+            aim_offset: 0.,
+        }
+    }
+
+    /// Swaps the weapon for another one with a full magazine, the bullets already in the air
+    /// still count.
+    pub fn equip(&mut self, weapon: Weapon) {
+        self.ammo = weapon.magazine;
+        self.weapon = weapon;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ammo == Some(0)
+    }
+
+    /// Whether there's ammo left and room on screen for all the bullets of another shot.
+    pub fn can_fire(&self) -> bool {
+        !self.is_empty() && self.shots_fired + self.weapon.pellets as i32 <= self.weapon.max_bullets
+    }
+
+    /// Keeps count of the bullets of a shot that was just fired.
+    pub fn fired(&mut self) {
+        self.shots_fired += self.weapon.pellets as i32;
+        self.spawned_bullet = true;
+        if let Some(ammo) = self.ammo.as_mut() {
+            *ammo = ammo.saturating_sub(1);
         }
     }
 }
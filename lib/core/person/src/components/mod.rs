@@ -2,6 +2,6 @@ mod bullet;
 mod gun;
 mod person;
 
-pub use self::bullet::{Bullet, BulletImpact};
+pub use self::bullet::{Bullet, BulletImpact, BulletLifetime};
 pub use self::gun::{Gun, GunState};
 pub use self::person::{Person, PersonState};
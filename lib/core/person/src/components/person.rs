@@ -3,8 +3,15 @@ use amethyst::{
     ecs::{Component, DenseVecStorage},
 };
 
+use score::KillCause;
+
 const PERSON_HEIGHT: f32 = 16.0;
 const PERSON_WIDTH: f32 = 16.0;
+// how long a jump kick keeps somebody down
+const STUN_SECONDS: f32 = 1.5;
+// ducking makes him a smaller target, jumping stretches him out (ie. for a kick)
+const DUCKING_HEIGHT_FACTOR: f32 = 0.5;
+const JUMPING_EXTRA_WIDTH: f32 = 4.0;
 
 #[allow(dead_code)] // TODO: remove when all variants are finished
 #[derive(Eq, Hash, PartialEq, Clone, Copy)]
@@ -19,6 +26,9 @@ pub enum PersonState {
     EnteringRoom,
     InsideRoom,
     ExitingRoom,
+    RidingEscalator,
+    /// flat on his back after a jump kick, until he gets back up
+    KnockedDown,
 }
 
 impl Default for PersonState {
@@ -27,6 +37,28 @@ impl Default for PersonState {
     }
 }
 
+impl PersonState {
+    /// The size of the hit box for his stance, `width` and `height` are the size of his
+    /// bounding box.
+    pub fn hit_box_size(self, width: f32, height: f32) -> Vector2<f32> {
+        match self {
+            PersonState::Ducking | PersonState::KnockedDown => {
+                Vector2::new(width, height * DUCKING_HEIGHT_FACTOR)
+            }
+            PersonState::Jumping => Vector2::new(width + JUMPING_EXTRA_WIDTH, height),
+            _ => Vector2::new(width, height),
+        }
+    }
+
+    /// Going into, staying in or coming out of a room behind a door.
+    pub fn is_in_room(self) -> bool {
+        match self {
+            PersonState::EnteringRoom | PersonState::InsideRoom | PersonState::ExitingRoom => true,
+            _ => false,
+        }
+    }
+}
+
 // TODO: does all of this stuff really need to be public?
 #[derive(Component)]
 #[storage(DenseVecStorage)]
@@ -39,6 +71,12 @@ pub struct Person {
     pub max_jump_velocity: f32,
     pub position: Vector2<f32>,
     pub ride_velocity: Vector2<f32>,
+    /// delete the person (and its children) once the die animation is done
+    pub remove_when_dead: bool,
+    /// what killed him, set while he is dying
+    pub killed_by: Option<KillCause>,
+    /// seconds left before he gets back up after being knocked down
+    pub stun_seconds: f32,
 }
 
 impl Default for Person {
@@ -58,6 +96,9 @@ impl Person {
             max_jump_velocity: 110.,
             position: Vector2::new(0., 0.),
             ride_velocity: Vector2::new(0., 0.),
+            remove_when_dead: true,
+            killed_by: None,
+            stun_seconds: 0.,
         }
     }
 
@@ -66,6 +107,26 @@ impl Person {
         self.position.y = y;
     }
 
+    /// Starts dying, the first thing to kill him is the cause.
+    pub fn kill(&mut self, cause: KillCause) {
+        if self.state != PersonState::Dying {
+            self.state = PersonState::Dying;
+            self.killed_by = Some(cause);
+        }
+    }
+
+    /// Knocks him down for a moment, returns false when he is already down or dying.
+    pub fn knock_down(&mut self) -> bool {
+        match self.state {
+            PersonState::Dying | PersonState::KnockedDown => false,
+            _ => {
+                self.state = PersonState::KnockedDown;
+                self.stun_seconds = STUN_SECONDS;
+                true
+            }
+        }
+    }
+
     pub fn update_ride_velocity(&mut self, x: f32, y: f32) {
         self.ride_velocity.x = x;
         self.ride_velocity.y = y;
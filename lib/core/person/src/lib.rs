@@ -1,6 +1,9 @@
 mod bullet;
 pub mod components;
 pub mod systems;
+mod weapon;
 
+pub use self::bullet::despawn_bullet;
 pub use self::bullet::show_bullet_impact;
-pub use self::bullet::spawn_bullet;
+pub use self::bullet::{spawn_bullet, BulletSpawn};
+pub use self::weapon::{Weapon, Weapons};
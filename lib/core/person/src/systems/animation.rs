@@ -69,7 +69,7 @@ impl<'s> System<'s> for GunAnimationSystem {
             .join()
         {
             let new_animation_id = match gun.state {
-                GunState::Shooting => AnimationId::PersonShoot,
+                GunState::Shooting => gun.weapon.animation,
                 GunState::JumpShooting => AnimationId::PersonJumpShoot,
                 _ => AnimationId::Holster,
             };
@@ -112,10 +112,13 @@ impl<'s> System<'s> for PersonAnimationSystem {
             let new_animation_id = match person.state {
                 PersonState::Hopping => AnimationId::Hop,
                 PersonState::Jumping => AnimationId::Jump,
-                PersonState::Walking => AnimationId::Walk,
+                PersonState::Walking
+                | PersonState::EnteringRoom
+                | PersonState::ExitingRoom
+                | PersonState::RidingEscalator => AnimationId::Walk,
                 PersonState::Shooting => AnimationId::Shoot,
                 PersonState::Dying => AnimationId::Die,
-                PersonState::Ducking => AnimationId::Duck,
+                PersonState::Ducking | PersonState::KnockedDown => AnimationId::Duck,
                 _ => AnimationId::Idle,
             };
 
@@ -137,7 +140,7 @@ impl<'s> System<'s> for PersonAnimationSystem {
                     .iter()
                     .find(|(id, _)| *id == AnimationId::Die);
 
-                if die_animation.is_none() {
+                if die_animation.is_none() && person.remove_when_dead {
                     // need to get rid of any children
                     for (child_entity, child) in (&entities, &children).join() {
                         if child.parent == entity {
@@ -1,10 +1,15 @@
-use amethyst::ecs::{Entities, Join, LazyUpdate, ReadExpect, ReadStorage, System, WriteStorage};
+use amethyst::{
+    ecs::{Entities, Join, LazyUpdate, Read, ReadExpect, ReadStorage, System, Write, WriteStorage},
+    shrev::EventChannel,
+};
 
 use crate::components::{Bullet, Gun, Person, PersonState};
 use physics::components::{Collidee, Collider, Motion};
 
-use crate::bullet::show_bullet_impact;
+use crate::bullet::{despawn_bullet, show_bullet_impact};
 use asset::{AssetType, PrefabList};
+use floors::FloorsDrawn;
+use score::{Bounty, KillCause, ScoreEvent};
 
 const IMPACT_OFFSET_X: f32 = -8.;
 
@@ -14,31 +19,102 @@ impl<'s> System<'s> for BulletCollisionSystem {
     type SystemData = (
         Entities<'s>,
         WriteStorage<'s, Person>,
-        ReadStorage<'s, Bullet>,
+        WriteStorage<'s, Bullet>,
         WriteStorage<'s, Gun>,
         ReadStorage<'s, Collider>,
-        ReadStorage<'s, Collidee>,
+        WriteStorage<'s, Collidee>,
         WriteStorage<'s, Motion>,
+        ReadStorage<'s, Bounty>,
+        Read<'s, FloorsDrawn>,
         ReadExpect<'s, PrefabList>,
         ReadExpect<'s, LazyUpdate>,
+        Write<'s, EventChannel<ScoreEvent>>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
         let (
             entities,
             mut persons,
-            bullets,
+            mut bullets,
             mut guns,
             colliders,
-            collidees,
+            mut collidees,
             mut motions,
+            bounties,
+            floors_drawn,
             prefab_list,
             lazy_update,
+            mut score_events,
         ) = data;
 
-        for (entity, bullet, collider, collidee, motion) in
-            (&*entities, &bullets, &colliders, &collidees, &mut motions).join()
+        for (entity, bullet, collider, collidee, motion) in (
+            &*entities,
+            &mut bullets,
+            &colliders,
+            &mut collidees,
+            &mut motions,
+        )
+            .join()
         {
+            // somebody on an escalator can only be hit from the floor his middle is on
+            let passes_rider = collidee
+                .horizontal
+                .as_ref()
+                .map_or(false, |collidee_horizontal| {
+                    persons
+                        .get(collidee_horizontal.collided_with_entity)
+                        .filter(|person| person.state == PersonState::RidingEscalator)
+                        .map_or(false, |person| {
+                            let rider_floors = floors_drawn.find_floors(person.position, 0., 0.);
+                            !floors_drawn
+                                .find_floors(collider.bounding_box.position, 0., 0.)
+                                .iter()
+                                .any(|floor| rider_floors.contains(floor))
+                        })
+                });
+            // the hit box follows his stance, a bullet can go over somebody ducking
+            let misses_stance = collidee
+                .horizontal
+                .as_ref()
+                .map_or(false, |collidee_horizontal| {
+                    let other = collidee_horizontal.collided_with_entity;
+                    persons.contains(other)
+                        && colliders.get(other).map_or(false, |person_collider| {
+                            !collider.is_overlapping_with(person_collider, true)
+                        })
+                });
+            // nobody can be shot while hidden inside a room, and bullets go through the dead
+            let passes_unhittable =
+                collidee
+                    .horizontal
+                    .as_ref()
+                    .map_or(false, |collidee_horizontal| {
+                        persons
+                            .get(collidee_horizontal.collided_with_entity)
+                            .map_or(false, |person| {
+                                person.state == PersonState::InsideRoom
+                                    || person.state == PersonState::Dying
+                            })
+                    });
+            if passes_rider || misses_stance || passes_unhittable {
+                collidee.horizontal = None;
+                continue;
+            }
+
+            // a bullet of a gun with spread can run into the floor or the ceiling
+            let hits_floor = motion.velocity.y != 0.
+                && collidee.horizontal.is_none()
+                && collidee
+                    .vertical
+                    .as_ref()
+                    .map_or(false, |collidee_vertical| {
+                        !persons.contains(collidee_vertical.collided_with_entity)
+                    });
+            if hits_floor {
+                despawn_bullet(&entities, &mut guns, entity, bullet);
+                continue;
+            }
+
             // Currently, bullet can be fired only horizontally
             if let Some(collidee_horizontal) = &collidee.horizontal {
                 let bullet_impact_prefab_handle =
@@ -49,10 +125,20 @@ impl<'s> System<'s> for BulletCollisionSystem {
                     collidee_horizontal.position.x - IMPACT_OFFSET_X
                 };
                 if collidee_horizontal.name == "Enemy" || collidee_horizontal.name == "Player" {
+                    let shot_by_player = bullet
+                        .parent
+                        .and_then(|parent| guns.get(parent))
+                        .map_or(false, |gun| gun.is_player);
                     // they should die
                     for (person_entity, person) in (&entities, &mut persons).join() {
                         if person_entity == collidee_horizontal.collided_with_entity {
-                            person.state = PersonState::Dying;
+                            if shot_by_player && collidee_horizontal.name == "Enemy" {
+                                score_events.single_write(ScoreEvent::enemy_killed(
+                                    KillCause::Bullet,
+                                    bounties.get(person_entity),
+                                ));
+                            }
+                            person.kill(KillCause::Bullet);
                             motion.velocity.x = 0.;
                             break;
                         }
@@ -66,15 +152,7 @@ impl<'s> System<'s> for BulletCollisionSystem {
                     motion.velocity.x,
                     &lazy_update,
                 );
-                // get the gun to remove a shot
-                for (entity, gun) in (&entities, &mut guns).join() {
-                    if let Some(parent) = bullet.parent {
-                        if parent == entity {
-                            gun.shots_fired -= 1;
-                        }
-                    }
-                }
-                let _ = entities.delete(entity);
+                despawn_bullet(&entities, &mut guns, entity, bullet);
             }
         }
     }
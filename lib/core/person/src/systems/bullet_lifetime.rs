@@ -0,0 +1,60 @@
+use amethyst::{
+    core::timing::Time,
+    ecs::{Entities, Join, Read, ReadStorage, System, WriteStorage},
+};
+
+use crate::bullet::despawn_bullet;
+use crate::components::{Bullet, BulletLifetime, Gun};
+use floors::FloorsDrawn;
+use physics::components::{Collider, Motion};
+
+/// Gets rid of the bullets that didn't hit anything, once they run out of time or range or
+/// leave the floors being drawn.
+pub struct BulletLifetimeSystem;
+
+impl<'s> System<'s> for BulletLifetimeSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Bullet>,
+        WriteStorage<'s, BulletLifetime>,
+        WriteStorage<'s, Gun>,
+        ReadStorage<'s, Collider>,
+        ReadStorage<'s, Motion>,
+        Read<'s, FloorsDrawn>,
+        Read<'s, Time>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            mut bullets,
+            mut lifetimes,
+            mut guns,
+            colliders,
+            motions,
+            floors_drawn,
+            time,
+        ) = data;
+        let delta_seconds = time.delta_seconds();
+
+        for (entity, bullet, lifetime, collider, motion) in (
+            &entities,
+            &mut bullets,
+            &mut lifetimes,
+            &colliders,
+            &motions,
+        )
+            .join()
+        {
+            lifetime.seconds_left -= delta_seconds;
+            lifetime.range_left -= motion.velocity.norm() * delta_seconds;
+            let on_screen = floors_drawn
+                .find_floors(collider.bounding_box.position, 0., 0.)
+                .iter()
+                .any(|floor| floors_drawn.rendered_floors.contains(floor));
+            if lifetime.seconds_left <= 0. || lifetime.range_left <= 0. || !on_screen {
+                despawn_bullet(&entities, &mut guns, entity, bullet);
+            }
+        }
+    }
+}
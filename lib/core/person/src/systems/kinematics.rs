@@ -26,6 +26,14 @@ impl<'s> System<'s> for PersonKinematicsSystem {
         for (collider, dir, person, motion) in
             (&mut colliders, &dirs, &persons, &mut motions).join()
         {
+            let size = collider.bounding_box.half_size * 2.;
+            let hit_box_size = person.state.hit_box_size(size.x, size.y);
+            collider.set_hit_box_size(hit_box_size.x, hit_box_size.y);
+
+            // the escalator sets the velocity of its riders
+            if person.state == PersonState::RidingEscalator {
+                continue;
+            }
             let mut acceleration = Vector2::new(0., 0.);
             match person.state {
                 PersonState::Idling | PersonState::Ducking => {
@@ -77,12 +85,21 @@ impl<'s> System<'s> for PersonKinematicsSystem {
                     motion.velocity.x = -motion.velocity.x;
                     acceleration = Vector2::new(0., GRAVITY_AMOUNT);
                 }
+                PersonState::KnockedDown => {
+                    // sliding back from the kick, whichever way he's facing
+                    let speed = (motion.velocity.x.abs() + FRICTION_AMOUNT).max(0.);
+                    motion.velocity.x = speed * motion.velocity.x.signum();
+                    motion.velocity.y += GRAVITY_AMOUNT;
+                    continue;
+                }
                 _ => {}
             }
             motion.update_velocity(acceleration, dir, 0., person.max_ground_speed);
             // move faster downward when on the elevator sperson he doesn't bounce due to gravity
             if collider.on_elevator
-                && (person.state == PersonState::Idling || person.state == PersonState::Walking)
+                && (person.state == PersonState::Idling
+                    || person.state == PersonState::Walking
+                    || person.state == PersonState::Ducking)
             {
                 motion.velocity.y = -40.;
             }
@@ -0,0 +1,101 @@
+use amethyst::{
+    core::{timing::Time, Named},
+    ecs::{Entities, Entity, Join, Read, ReadStorage, System, WriteStorage},
+};
+
+use crate::components::{Person, PersonState};
+use floors::Floor;
+use physics::components::{Collider, Motion};
+
+// how fast somebody slides back from a jump kick, and how far up it throws him
+const KNOCKBACK_VELOCITY: f32 = 120.0;
+const KNOCKBACK_HOP: f32 = 40.0;
+
+/// Jump kicks, the `CollisionSystem` lets people walk through each other so their hit boxes
+/// are checked here.
+///
+/// Somebody jumping into a person from the other side on the same floor knocks him down, he
+/// gets back up once he has landed and the stun has worn off.
+pub struct MeleeSystem;
+
+impl<'s> System<'s> for MeleeSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Person>,
+        ReadStorage<'s, Collider>,
+        WriteStorage<'s, Motion>,
+        ReadStorage<'s, Floor>,
+        ReadStorage<'s, Named>,
+        Read<'s, Time>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, mut persons, colliders, mut motions, floors, names, time) = data;
+
+        // 1. getting back up
+        for (person, collider) in (&mut persons, &colliders).join() {
+            if person.state == PersonState::KnockedDown {
+                person.stun_seconds -= time.delta_seconds();
+                if person.stun_seconds <= 0. && collider.on_ground {
+                    person.state = PersonState::Idling;
+                }
+            }
+        }
+
+        // 2. who got kicked, and which way he goes
+        let mut kicks: Vec<(Entity, f32)> = Vec::new();
+        for (kicker_entity, kicker, kicker_collider, kicker_motion, kicker_floor, kicker_named) in
+            (&entities, &persons, &colliders, &motions, &floors, &names).join()
+        {
+            let kick_velocity = kicker_motion.velocity.x;
+            if kicker.state != PersonState::Jumping
+                || kicker_collider.on_ground
+                || kick_velocity == 0.
+            {
+                continue;
+            }
+            for (entity, person, collider, floor, named) in
+                (&entities, &persons, &colliders, &floors, &names).join()
+            {
+                let towards =
+                    collider.bounding_box.position.x - kicker_collider.bounding_box.position.x;
+                let can_be_kicked = match person.state {
+                    PersonState::Dying
+                    | PersonState::KnockedDown
+                    | PersonState::RidingEscalator => false,
+                    state => !state.is_in_room(),
+                };
+                // nobody kicks his own side
+                if entity != kicker_entity
+                    && named.name != kicker_named.name
+                    && can_be_kicked
+                    && towards * kick_velocity > 0.
+                    && floor
+                        .floors_overlapped
+                        .iter()
+                        .any(|f| kicker_floor.contains(f))
+                    && kicker_collider.is_overlapping_with(collider, true)
+                {
+                    kicks.push((entity, kick_velocity.signum()));
+                }
+            }
+        }
+
+        // 3. knock them down
+        for (entity, direction) in kicks {
+            let knocked_down = persons
+                .get_mut(entity)
+                .map_or(false, |person| person.knock_down());
+            if !knocked_down {
+                continue;
+            }
+            if let Some(named) = names.get(entity) {
+                println!("### {} knocked down ###", named.name);
+            }
+            if let Some(motion) = motions.get_mut(entity) {
+                motion.velocity.x = direction * KNOCKBACK_VELOCITY;
+                motion.velocity.y = KNOCKBACK_HOP;
+            }
+        }
+    }
+}
@@ -1,7 +1,9 @@
 mod animation;
 mod bullet_collision;
+mod bullet_lifetime;
 mod floor;
 mod kinematics;
+mod melee;
 mod shoot;
 mod transformation;
 
@@ -9,8 +11,10 @@ pub use self::animation::BulletImpactAnimationSystem;
 pub use self::animation::GunAnimationSystem;
 pub use self::animation::PersonAnimationSystem;
 pub use self::bullet_collision::BulletCollisionSystem;
+pub use self::bullet_lifetime::BulletLifetimeSystem;
 pub use self::floor::PersonFloorSystem;
 pub use self::kinematics::PersonKinematicsSystem;
+pub use self::melee::MeleeSystem;
 pub use self::shoot::ShootSystem;
 pub use self::transformation::GunTransformationSystem;
 pub use self::transformation::PersonTransformationSystem;
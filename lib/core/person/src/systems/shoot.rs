@@ -1,10 +1,15 @@
-use amethyst::ecs::{Entities, Join, LazyUpdate, ReadExpect, ReadStorage, System, WriteStorage};
+use amethyst::{
+    core::math::Vector2,
+    ecs::{Entities, Join, LazyUpdate, ReadExpect, ReadStorage, System, Write, WriteStorage},
+};
+use rand::Rng;
 
-use crate::bullet::spawn_bullet;
+use crate::bullet::{spawn_bullet, BulletSpawn};
 use crate::components::{Gun, GunState, Person};
 use asset::{AssetType, SpriteSheetList};
 use hierarchy::components::Child;
 use physics::components::Direction;
+use random::{GameRng, RngStream};
 
 pub struct ShootSystem;
 
@@ -17,11 +22,21 @@ impl<'s> System<'s> for ShootSystem {
         ReadStorage<'s, Direction>,
         ReadExpect<'s, SpriteSheetList>,
         ReadExpect<'s, LazyUpdate>,
+        Write<'s, GameRng>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (entities, mut guns, children, persons, directions, sprite_sheet_list, lazy_update) =
-            data;
+        let (
+            entities,
+            mut guns,
+            children,
+            persons,
+            directions,
+            sprite_sheet_list,
+            lazy_update,
+            mut game_rng,
+        ) = data;
+        let rng = game_rng.stream(RngStream::Weapons);
 
         for (gun_entity, gun, child, direction) in
             (&entities, &mut guns, &children, &directions).join()
@@ -32,22 +47,32 @@ impl<'s> System<'s> for ShootSystem {
                     && !gun.spawned_bullet
                     && (gun.state == GunState::Shooting || gun.state == GunState::JumpShooting)
                 {
-                    let pos_x = person.position.x;
-                    let pos_y = person.position.y;
+                    let position =
+                        Vector2::new(person.position.x, person.position.y + gun.aim_offset);
 
-                    let bullet_sprite_sheet_handle =
-                        { sprite_sheet_list.get(AssetType::Bullet).unwrap().clone() };
-                    spawn_bullet(
-                        &entities,
-                        gun_entity,
-                        bullet_sprite_sheet_handle,
-                        pos_x,
-                        pos_y,
-                        direction,
-                        &lazy_update,
-                    );
-                    gun.shots_fired += 1;
-                    gun.spawned_bullet = true;
+                    // every pellet goes its own way within the spread
+                    for _ in 0..gun.weapon.pellets {
+                        let angle = if gun.weapon.spread > 0. {
+                            rng.gen_range(-gun.weapon.spread, gun.weapon.spread)
+                        } else {
+                            0.
+                        };
+                        let bullet_sprite_sheet_handle =
+                            { sprite_sheet_list.get(AssetType::Bullet).unwrap().clone() };
+                        spawn_bullet(
+                            &entities,
+                            gun_entity,
+                            bullet_sprite_sheet_handle,
+                            BulletSpawn {
+                                position,
+                                weapon: &gun.weapon,
+                                angle,
+                                direction,
+                            },
+                            &lazy_update,
+                        );
+                    }
+                    gun.fired();
                 }
             }
         }
@@ -8,6 +8,9 @@ use hierarchy::components::Child;
 use physics::components::{Collidee, Collider, Direction, Motion};
 
 const SAFE_PADDING: f32 = 0.1;
+const PERSON_Z: f32 = 0.5;
+// riders go behind the people walking on the floors they pass
+const RIDING_Z: f32 = 0.4;
 
 pub struct PersonTransformationSystem;
 
@@ -32,6 +35,20 @@ impl<'s> System<'s> for PersonTransformationSystem {
         )
             .join()
         {
+            // the steps go right through the floors, so nothing stops a rider
+            if player.state == PersonState::RidingEscalator {
+                collidee.horizontal = None;
+                collidee.vertical = None;
+                let position = collider.bounding_box.position;
+                collider.set_hit_box_position(motion.velocity);
+                player.update_ride_velocity(0., 0.);
+                collider.on_elevator = false;
+                collider.riding = None;
+                player.update_position(position.x, position.y);
+                transform.set_translation_xyz(position.x, position.y, RIDING_Z);
+                continue;
+            }
+
             // find anything that the player is colliding with
             let bbox = &mut collider.bounding_box;
             let velocity = &mut motion.velocity;
@@ -69,7 +86,9 @@ impl<'s> System<'s> for PersonTransformationSystem {
                             + collidee_vertical.half_size.y
                             + bbox.half_size.y
                             + SAFE_PADDING;
-                    } else {
+                    } else if player.state != PersonState::Dying {
+                        // pushed down, unless he got crushed under it, then he stays on the
+                        // floor instead of going through it
                         bbox.position.y = collidee_vertical.position.y
                             - collidee_vertical.half_size.y
                             - bbox.half_size.y
@@ -87,10 +106,14 @@ impl<'s> System<'s> for PersonTransformationSystem {
                 {
                     player.update_ride_velocity(0., collidee_vertical.collided_with_velocity);
                     collider.on_elevator = true;
+                    collider.riding = Some(collidee_vertical.collided_with_entity);
+                } else {
+                    collider.riding = None;
                 }
             } else {
                 player.update_ride_velocity(0., 0.);
                 collider.on_elevator = false;
+                collider.riding = None;
             }
 
             if velocity.y != 0. {
@@ -101,14 +124,15 @@ impl<'s> System<'s> for PersonTransformationSystem {
             let mut y = bbox.position.y;
 
             collider.set_hit_box_position(*velocity);
-            if player.state == PersonState::Ducking {
+            if player.state == PersonState::Ducking || player.state == PersonState::KnockedDown {
                 y -= 4.0;
             }
 
-            if player.state == PersonState::EnteringRoom {
+            // behind the open door on the way in and out of a room
+            if player.state.is_in_room() {
                 transform.set_translation_z(0.);
             } else {
-                transform.set_translation_z(0.5);
+                transform.set_translation_z(PERSON_Z);
             }
 
             player.update_position(x, y);
@@ -148,8 +172,10 @@ impl<'s> System<'s> for GunTransformationSystem {
                     }
                     transform.set_translation_y(person.position.y + child.offset_y);
 
-                    if person.state == PersonState::EnteringRoom {
+                    if person.state.is_in_room() {
                         transform.set_translation_z(0.);
+                    } else if person.state == PersonState::RidingEscalator {
+                        transform.set_translation_z(RIDING_Z + 0.05);
                     } else {
                         transform.set_translation_z(0.7);
                     }
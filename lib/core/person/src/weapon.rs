@@ -0,0 +1,112 @@
+use std::{fs::File, path::Path};
+
+use amethyst::error::Error;
+use serde::{Deserialize, Serialize};
+
+use animation::components::AnimationId;
+
+/// A kind of gun, like the pistol everybody starts with or the shotgun found in a room.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Weapon {
+    pub name: String,
+    /// shots before it's used up and the pistol comes back out, it never runs out when `None`
+    #[serde(default)]
+    pub magazine: Option<u32>,
+    /// bullets of this gun that can be in the air at the same time
+    pub max_bullets: i32,
+    /// seconds between two shots
+    pub fire_interval: f64,
+    /// keeps on firing while the button is held down
+    #[serde(default)]
+    pub automatic: bool,
+    pub bullet_velocity: f32,
+    /// how far and how long a bullet flies before it's gone
+    pub range: f32,
+    pub lifetime: f32,
+    /// degrees above or below straight ahead a bullet can go
+    #[serde(default)]
+    pub spread: f32,
+    /// bullets that come out with every shot
+    pub pellets: u32,
+    /// what the gun looks like while shooting
+    pub animation: AnimationId,
+}
+
+impl Default for Weapon {
+    fn default() -> Self {
+        Weapon {
+            name: String::from("pistol"),
+            magazine: None,
+            max_bullets: 3,
+            fire_interval: 0.1,
+            automatic: false,
+            bullet_velocity: 200.,
+            range: 256.,
+            lifetime: 2.,
+            spread: 0.,
+            pellets: 1,
+            animation: AnimationId::PersonShoot,
+        }
+    }
+}
+
+/// All the kinds of guns, loaded from `assets/weapons.ron`, the player starts out with the
+/// first one.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Weapons {
+    pub weapons: Vec<Weapon>,
+}
+
+impl Default for Weapons {
+    fn default() -> Self {
+        Weapons {
+            weapons: vec![Weapon::default()],
+        }
+    }
+}
+
+impl Weapons {
+    pub fn load(path: &Path) -> Result<Weapons, Error> {
+        let file = File::open(path)?;
+        let weapons: Weapons = ron::de::from_reader(file)?;
+        if weapons.weapons.is_empty() {
+            return Err(Error::from_string(format!("No weapons in {:?}", path)));
+        }
+        for weapon in weapons.weapons.iter() {
+            if weapon.pellets == 0 || weapon.pellets as i32 > weapon.max_bullets {
+                return Err(Error::from_string(format!(
+                    "Weapon {} in {:?} can never fire",
+                    weapon.name, path
+                )));
+            }
+            // a shot has to go forwards, and a used up magazine would swap the gun straight back
+            let problem = if !(0. ..90.).contains(&weapon.spread) {
+                Some("a spread of at least 0 and under 90 degrees")
+            } else if weapon.fire_interval.is_nan() || weapon.fire_interval < 0. {
+                Some("a fire interval of at least 0")
+            } else if weapon.bullet_velocity.is_nan() || weapon.bullet_velocity <= 0. {
+                Some("a bullet velocity above 0")
+            } else if weapon.magazine == Some(0) {
+                Some("at least one shot in the magazine")
+            } else {
+                None
+            };
+            if let Some(problem) = problem {
+                return Err(Error::from_string(format!(
+                    "Weapon {} in {:?} needs {}",
+                    weapon.name, path, problem
+                )));
+            }
+        }
+        Ok(weapons)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Weapon> {
+        self.weapons.iter().find(|weapon| weapon.name == name)
+    }
+
+    /// The gun the player starts with and falls back to when another one is used up.
+    pub fn starting(&self) -> &Weapon {
+        &self.weapons[0]
+    }
+}
@@ -47,6 +47,7 @@ pub struct CollideeDetails {
 pub struct ProximityDetails {
     pub name: String,
     pub other_name: String,
+    pub other_entity: Entity,
     pub distance: Vector2<f32>,
     pub approaching: bool,
 }
@@ -70,6 +71,13 @@ impl Default for Proximity {
 }
 
 impl Proximity {
+    pub fn new(min_distance: f32) -> Self {
+        Proximity {
+            min_distance,
+            details: Vec::new(),
+        }
+    }
+
     pub fn reset_details(&mut self) {
         self.details = Vec::new();
     }
@@ -78,6 +86,7 @@ impl Proximity {
         &mut self,
         name_a: String,
         name_b: String,
+        entity_b: Entity,
         collider_a: &Collider,
         collider_b: &Collider,
         velocity_a: Vector2<f32>,
@@ -114,6 +123,7 @@ impl Proximity {
             self.details.push(ProximityDetails {
                 name: name_a,
                 other_name: name_b,
+                other_entity: entity_b,
                 distance: Vector2::new(x_diff, y_diff),
                 approaching,
             });
@@ -254,12 +264,16 @@ pub struct Collider {
     pub hit_box_offset: Vector2<f32>,
     pub on_ground: bool,
     pub on_elevator: bool,
+    /// the rideable thing (ie. an elevator floor or roof) being stood on
+    pub riding: Option<Entity>,
     pub hit_box_offset_front: f32,
     pub hit_box_offset_back: f32,
     pub is_collidable: bool,
     pub is_rideable: bool,
     pub is_person: bool,
     pub allow_proximity: bool,
+    /// what fired it (ie. the gun of a bullet), things with the same owner go through each other
+    pub owner: Option<Entity>,
 }
 
 impl Default for Collider {
@@ -271,12 +285,14 @@ impl Default for Collider {
             hit_box_offset: Vector2::new(0., 0.),
             on_ground: false,
             on_elevator: false,
+            riding: None,
             hit_box_offset_front: 0.,
             hit_box_offset_back: 0.,
             is_collidable: true,
             is_rideable: false,
             is_person: false,
             allow_proximity: true,
+            owner: None,
         }
     }
 }
@@ -290,18 +306,25 @@ impl Collider {
         }
     }
 
+    /// Resizes the hit box, it stays level with the bottom of the bounding box.
+    pub fn set_hit_box_size(&mut self, width: f32, height: f32) {
+        self.hit_box.half_size = Vector2::new(width / 2., height / 2.);
+    }
+
     pub fn set_hit_box_position(&mut self, velocity: Vector2<f32>) {
         let hbox_position = &mut self.hit_box.position;
         let bbox_position = self.bounding_box.position;
+        // a hit box that is shorter or taller than the bounding box still stands on its bottom
+        let bottom_y = bbox_position.y - self.bounding_box.half_size.y + self.hit_box.half_size.y;
         hbox_position.x = if velocity.x >= 0. {
             bbox_position.x + self.hit_box_offset.x
         } else {
             bbox_position.x - self.hit_box_offset.x
         };
         hbox_position.y = if velocity.y >= 0. {
-            bbox_position.y + self.hit_box_offset.y
+            bottom_y + self.hit_box_offset.y
         } else {
-            bbox_position.y - self.hit_box_offset.y
+            bottom_y - self.hit_box_offset.y
         }
     }
 
@@ -3,7 +3,9 @@ mod direction;
 mod motion;
 mod transformation;
 
-pub use self::collision::{Collidee, CollideeDetails, Collider, Proximity, ProximityDetails};
+pub use self::collision::{
+    Collidee, CollideeDetails, Collider, GenericBox, Proximity, ProximityDetails,
+};
 pub use self::direction::{Direction, Directions};
 pub use self::motion::Motion;
 pub use self::transformation::DefaultTransformation;
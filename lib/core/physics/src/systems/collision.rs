@@ -30,10 +30,12 @@ impl<'s> System<'s> for CollisionSystem {
                     let velocity_b = motion_b.velocity;
                     let use_hit_box =
                         (velocity_a.x * velocity_b.x != 0.) || (velocity_a.y * velocity_b.y != 0.);
-                    // can't be the same, must be collidable, can't both be people
+                    // can't be the same, must be collidable, can't both be people or bullets of the
+                    // same gun
                     if entity_a != entity_b
                         && collider_b.is_collidable
                         && (!collider_a.is_person || !collider_b.is_person)
+                        && (collider_a.owner.is_none() || collider_a.owner != collider_b.owner)
                     {
                         if collider_a.is_overlapping_with(collider_b, use_hit_box) {
                             collidee.set_collidee_details(
@@ -84,6 +86,7 @@ impl<'s> System<'s> for ProximitySystem {
                         proximity_a.add_proximity_details(
                             name_a.name.to_string(),
                             name_b.name.to_string(),
+                            entity_b,
                             collider_a,
                             collider_b,
                             velocity_a,
@@ -0,0 +1,3 @@
+mod rng;
+
+pub use self::rng::{GameRng, RngStream};
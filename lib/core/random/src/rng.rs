@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Each gameplay subsystem draws from its own stream so that adding randomness to one of
+/// them doesn't change the decisions made by the others.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RngStream {
+    EnemyAi,
+    EnemySpawn,
+    EnemyCombat,
+    Weapons,
+}
+
+impl RngStream {
+    fn salt(self) -> u64 {
+        match self {
+            RngStream::EnemyAi => 1,
+            RngStream::EnemySpawn => 2,
+            RngStream::EnemyCombat => 3,
+            RngStream::Weapons => 4,
+        }
+    }
+}
+
+/// The seeded random number generator that all gameplay randomness goes through.
+pub struct GameRng {
+    seed: u64,
+    streams: HashMap<RngStream, StdRng>,
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(rand::random())
+    }
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        GameRng {
+            seed,
+            streams: HashMap::new(),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the generator for a subsystem, creating it from the seed the first time.
+    pub fn stream(&mut self, stream: RngStream) -> &mut StdRng {
+        let seed = self.seed;
+        self.streams.entry(stream).or_insert_with(|| {
+            StdRng::seed_from_u64(seed ^ stream.salt().wrapping_mul(0x9E37_79B9_7F4A_7C15))
+        })
+    }
+}
@@ -0,0 +1,160 @@
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+};
+
+use amethyst::error::Error;
+use serde::{Deserialize, Serialize};
+
+pub const MAX_HIGH_SCORES: usize = 5;
+pub const INITIALS_LENGTH: usize = 3;
+const DATA_DIRECTORY: &str = "elevator_rs";
+const FILE_NAME: &str = "high_scores.ron";
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct HighScore {
+    pub initials: String,
+    pub score: u32,
+}
+
+/// The best scores, best first, kept in the user's data directory between games.
+///
+/// When the file can't be read or written the table still works, it just isn't kept.
+#[derive(Debug, Default)]
+pub struct HighScores {
+    pub entries: Vec<HighScore>,
+    path: Option<PathBuf>,
+}
+
+impl HighScores {
+    /// Loads the table from the user's data directory, starting an empty one if that fails.
+    pub fn load_or_default() -> Self {
+        let path = match dirs::data_dir() {
+            Some(data_dir) => data_dir.join(DATA_DIRECTORY).join(FILE_NAME),
+            None => {
+                println!("No data directory, high scores will not be saved");
+                return HighScores::default();
+            }
+        };
+        let entries = if path.exists() {
+            match load_entries(&path) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    println!("Failed to load high scores from {:?}: {}", path, e);
+                    Vec::new()
+                }
+            }
+        } else {
+            Vec::new()
+        };
+        let mut high_scores = HighScores {
+            entries,
+            path: Some(path),
+        };
+        high_scores.sort();
+        high_scores
+    }
+
+    /// The best score, 0 when there are none yet.
+    pub fn best(&self) -> u32 {
+        self.entries.first().map_or(0, |entry| entry.score)
+    }
+
+    /// Whether the score is good enough to be added to the table.
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0
+            && (self.entries.len() < MAX_HIGH_SCORES
+                || self.entries.iter().any(|entry| score > entry.score))
+    }
+
+    /// Adds the score to the table and saves it, a failed save only keeps it in memory.
+    pub fn insert(&mut self, initials: &str, score: u32) {
+        self.entries.push(HighScore {
+            initials: String::from(initials),
+            score,
+        });
+        self.sort();
+        if let Some(path) = &self.path {
+            if let Err(e) = save_entries(path, &self.entries) {
+                println!("Failed to save high scores to {:?}: {}", path, e);
+            }
+        }
+    }
+
+    fn sort(&mut self) {
+        self.entries.sort_by(|a, b| b.score.cmp(&a.score));
+        self.entries.truncate(MAX_HIGH_SCORES);
+    }
+}
+
+fn load_entries(path: &Path) -> Result<Vec<HighScore>, Error> {
+    let file = File::open(path)?;
+    Ok(ron::de::from_reader(file)?)
+}
+
+fn save_entries(path: &Path, entries: &[HighScore]) -> Result<(), Error> {
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    fs::write(path, ron::ser::to_string(&entries)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scores(high_scores: &HighScores) -> Vec<u32> {
+        high_scores
+            .entries
+            .iter()
+            .map(|entry| entry.score)
+            .collect()
+    }
+
+    #[test]
+    fn keeps_best_first() {
+        let mut high_scores = HighScores::default();
+        high_scores.insert("AAA", 300);
+        high_scores.insert("BBB", 500);
+        high_scores.insert("CCC", 100);
+        assert_eq!(scores(&high_scores), vec![500, 300, 100]);
+        assert_eq!(high_scores.entries[0].initials, "BBB");
+        assert_eq!(high_scores.best(), 500);
+    }
+
+    #[test]
+    fn trims_to_the_best_five() {
+        let mut high_scores = HighScores::default();
+        for score in 1..=MAX_HIGH_SCORES as u32 + 2 {
+            high_scores.insert("AAA", score * 100);
+        }
+        assert_eq!(scores(&high_scores), vec![700, 600, 500, 400, 300]);
+    }
+
+    #[test]
+    fn only_better_scores_qualify_once_full() {
+        let mut high_scores = HighScores::default();
+        assert!(!high_scores.qualifies(0));
+        assert!(high_scores.qualifies(10));
+        for _ in 0..MAX_HIGH_SCORES {
+            high_scores.insert("AAA", 100);
+        }
+        assert!(!high_scores.qualifies(100));
+        assert!(high_scores.qualifies(101));
+    }
+
+    #[test]
+    fn saves_and_loads_entries() {
+        let path = std::env::temp_dir()
+            .join(format!("{}_test_{}", DATA_DIRECTORY, std::process::id()))
+            .join(FILE_NAME);
+        let entries = vec![HighScore {
+            initials: String::from("ABC"),
+            score: 1200,
+        }];
+        save_entries(&path, &entries).unwrap();
+        assert_eq!(load_entries(&path).unwrap(), entries);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
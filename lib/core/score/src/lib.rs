@@ -0,0 +1,6 @@
+mod high_scores;
+mod score;
+pub mod systems;
+
+pub use self::high_scores::{HighScore, HighScores, INITIALS_LENGTH, MAX_HIGH_SCORES};
+pub use self::score::{Bounty, KillCause, Score, ScoreEvent};
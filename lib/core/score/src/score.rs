@@ -0,0 +1,98 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+// what an enemy is worth when he doesn't have a bounty of his own
+const ENEMY_POINTS: u32 = 100;
+// dropping a lamp on an enemy or crushing him with an elevator is worth more than shooting him
+const SPECIAL_KILL_FACTOR: u32 = 3;
+const DOCUMENTS_POINTS: u32 = 500;
+// clearing a level faster than this gives a time bonus for each second left
+const PAR_SECONDS: f32 = 300.;
+const TIME_BONUS_POINTS_PER_SECOND: u32 = 10;
+
+/// What killed a person, when it was an enemy the player gets points for it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KillCause {
+    Bullet,
+    Lamp,
+    Elevator,
+}
+
+/// The points an enemy is worth when he's shot, other kinds of kills multiply it.
+#[derive(Component, Clone, Copy, Debug)]
+#[storage(DenseVecStorage)]
+pub struct Bounty {
+    pub points: u32,
+}
+
+impl Default for Bounty {
+    fn default() -> Self {
+        Bounty::new(ENEMY_POINTS)
+    }
+}
+
+impl Bounty {
+    pub fn new(points: u32) -> Self {
+        Bounty { points }
+    }
+}
+
+/// Something the player did that is worth points, sent through an `EventChannel<ScoreEvent>`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScoreEvent {
+    EnemyKilled { cause: KillCause, bounty: u32 },
+    DocumentsCollected,
+    LevelCleared { seconds: f32 },
+}
+
+impl ScoreEvent {
+    /// An enemy was killed, `bounty` is his `Bounty` component if he has one.
+    pub fn enemy_killed(cause: KillCause, bounty: Option<&Bounty>) -> Self {
+        ScoreEvent::EnemyKilled {
+            cause,
+            bounty: bounty.cloned().unwrap_or_default().points,
+        }
+    }
+
+    pub fn points(&self) -> u32 {
+        match self {
+            ScoreEvent::EnemyKilled {
+                cause: KillCause::Bullet,
+                bounty,
+            } => *bounty,
+            ScoreEvent::EnemyKilled { bounty, .. } => bounty * SPECIAL_KILL_FACTOR,
+            ScoreEvent::DocumentsCollected => DOCUMENTS_POINTS,
+            ScoreEvent::LevelCleared { seconds } => {
+                let seconds_left = (PAR_SECONDS - seconds).max(0.) as u32;
+                seconds_left * TIME_BONUS_POINTS_PER_SECOND
+            }
+        }
+    }
+}
+
+/// The score of the current game and the best score so far.
+#[derive(Debug, Default)]
+pub struct Score {
+    pub score: u32,
+    pub high_score: u32,
+}
+
+impl Score {
+    pub fn new(high_score: u32) -> Self {
+        Score {
+            score: 0,
+            high_score,
+        }
+    }
+
+    pub fn add(&mut self, points: u32) {
+        self.score += points;
+        if self.score > self.high_score {
+            self.high_score = self.score;
+        }
+    }
+
+    /// Starts a new game, the high score is kept.
+    pub fn reset(&mut self) {
+        self.score = 0;
+    }
+}
@@ -0,0 +1,37 @@
+use amethyst::{
+    ecs::prelude::{Entity, Read, System, WriteStorage},
+    ui::{UiFinder, UiText},
+};
+
+use crate::score::Score;
+
+/// Shows the score and the high score in the labels of assets/ui/score.ron.
+#[derive(Default)]
+pub struct ScoreHudSystem {
+    score_display: Option<Entity>,
+    high_score_display: Option<Entity>,
+}
+
+impl<'a> System<'a> for ScoreHudSystem {
+    type SystemData = (Read<'a, Score>, WriteStorage<'a, UiText>, UiFinder<'a>);
+
+    fn run(&mut self, (score, mut ui_text, finder): Self::SystemData) {
+        // the labels are created again with every level, so look them up until they are found
+        match self
+            .score_display
+            .and_then(|entity| ui_text.get_mut(entity))
+        {
+            Some(score_display) => score_display.text = format!("SCORE {}", score.score),
+            None => self.score_display = finder.find("score_text"),
+        }
+        match self
+            .high_score_display
+            .and_then(|entity| ui_text.get_mut(entity))
+        {
+            Some(high_score_display) => {
+                high_score_display.text = format!("HIGH SCORE {}", score.high_score)
+            }
+            None => self.high_score_display = finder.find("high_score_text"),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod hud;
+mod score;
+
+pub use self::hud::ScoreHudSystem;
+pub use self::score::{ScoreSystem, ScoreSystemDesc};
@@ -0,0 +1,37 @@
+use amethyst::{
+    core::SystemDesc,
+    derive::SystemDesc,
+    ecs::{Read, System, SystemData, World, Write},
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::score::{Score, ScoreEvent};
+
+/// Adds up the points of every `ScoreEvent` sent during the frame.
+#[derive(SystemDesc)]
+#[system_desc(name(ScoreSystemDesc))]
+pub struct ScoreSystem {
+    #[system_desc(event_channel_reader)]
+    reader_id: ReaderId<ScoreEvent>,
+}
+
+impl ScoreSystem {
+    pub fn new(reader_id: ReaderId<ScoreEvent>) -> Self {
+        ScoreSystem { reader_id }
+    }
+}
+
+impl<'s> System<'s> for ScoreSystem {
+    type SystemData = (Read<'s, EventChannel<ScoreEvent>>, Write<'s, Score>);
+
+    fn run(&mut self, (score_events, mut score): Self::SystemData) {
+        for event in score_events.read(&mut self.reader_id) {
+            let points = event.points();
+            score.add(points);
+            println!(
+                "### {:?} scored {} points, score: {} ###",
+                event, points, score.score
+            );
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use amethyst::{
     core::math::Vector2,
-    ecs::{Component, DenseVecStorage, NullStorage},
+    ecs::{Component, DenseVecStorage, Entity, NullStorage},
 };
 
 #[allow(dead_code)] // TODO: remove when all variants are finished
@@ -17,18 +17,25 @@ pub struct Door {
     pub can_user_enter: bool,
     pub state: DoorState,
     pub has_papers: bool,
+    /// the weapon found in the room behind a blue door
+    pub pickup: Option<String>,
 }
 
 impl Door {
-    pub fn new(position: Vector2<f32>, can_user_enter: bool) -> Door {
-        let has_papers = can_user_enter;
+    pub fn new(position: Vector2<f32>, has_papers: bool, pickup: Option<String>) -> Door {
         Door {
             position,
-            can_user_enter,
+            can_user_enter: has_papers || pickup.is_some(),
             state: DoorState::Closed,
             has_papers,
+            pickup,
         }
     }
+
+    /// Red doors hold the documents, they turn blue once the room has been visited.
+    pub fn is_red(&self) -> bool {
+        self.can_user_enter && self.pickup.is_none()
+    }
 }
 
 #[derive(Component, Default)]
@@ -38,3 +45,20 @@ pub struct DoorEntry {}
 #[derive(Component, Default)]
 #[storage(NullStorage)]
 pub struct Room {}
+
+/// A person going through the room behind a door, from entering until he is back out.
+#[derive(Component)]
+#[storage(DenseVecStorage)]
+pub struct RoomVisit {
+    pub door: Entity,
+    pub elapsed_seconds: f32,
+}
+
+impl RoomVisit {
+    pub fn new(door: Entity) -> Self {
+        RoomVisit {
+            door,
+            elapsed_seconds: 0.,
+        }
+    }
+}
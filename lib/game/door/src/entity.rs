@@ -10,16 +10,38 @@ use floors::Floor;
 use hierarchy::components::Child;
 use physics::components::{Collidee, Collider, Direction, Directions, Motion};
 
+/// A door from the "doors" layer of the map, about to be drawn.
+pub struct DoorSpawn<'a> {
+    /// id of the object in the map
+    pub id: usize,
+    pub position: Vector2<f32>,
+    /// which colour the door is and which way it faces, like `red_left`
+    pub name: &'a str,
+    pub floors_overlapped: &'a Vec<usize>,
+    /// name of the weapon in the room, if there's one
+    pub pickup: Option<&'a str>,
+    /// the room was already visited
+    pub is_used: bool,
+}
+
 pub fn load_door(
-    id: usize,
     entities: &Entities,
     lazy_update: &ReadExpect<LazyUpdate>,
     prefab_handle: Handle<Prefab<AnimationPrefabData>>,
-    position: Vector2<f32>,
-    name: &str,
-    floors_overlapped: &Vec<usize>,
+    spawn: DoorSpawn,
 ) {
-    let can_user_enter = name == "red_left" || name == "red_right";
+    let DoorSpawn {
+        id,
+        position,
+        name,
+        floors_overlapped,
+        pickup,
+        is_used,
+    } = spawn;
+    // a red door turns blue for good once its room has been visited, a blue door with a weapon
+    // behind it can't be entered again either
+    let has_papers = (name == "red_left" || name == "red_right") && !is_used;
+    let pickup = pickup.filter(|_| !is_used).map(String::from);
     let mut collider = Collider::new(4., 28.); // door is narrower for collision sake t
     collider.is_collidable = false;
     collider.bounding_box.position.x = position.x; // adjust it slightly to prevent people walking past
@@ -27,12 +49,13 @@ pub fn load_door(
     let mut transform = Transform::default();
     // position in tilesheet is based on corner not middle, remember y is reversed (bottom to top)
     transform.set_translation_xyz(position.x, position.y, 0.25);
-    let door = Door::new(Vector2::new(position.x, position.y), can_user_enter);
+    let door = Door::new(Vector2::new(position.x, position.y), has_papers, pickup);
+    let can_user_enter = door.can_user_enter;
     println!(
         "Loading door! {:?}, can_user_enter: {}",
         position, can_user_enter
     );
-    let animation_id = if can_user_enter {
+    let animation_id = if door.is_red() {
         AnimationId::RedDoor
     } else {
         AnimationId::BlueDoor
@@ -2,4 +2,4 @@ pub mod components;
 pub mod systems;
 
 mod entity;
-pub use self::entity::load_door;
+pub use self::entity::{load_door, DoorSpawn};
@@ -32,14 +32,14 @@ impl<'s> System<'s> for DoorAnimationSystem {
         {
             let new_animation_id = match door.state {
                 DoorState::Open => {
-                    if door.can_user_enter {
+                    if door.is_red() {
                         AnimationId::RedDoorOpen
                     } else {
                         AnimationId::BlueDoorOpen
                     }
                 }
                 DoorState::Closed => {
-                    if door.can_user_enter {
+                    if door.is_red() {
                         AnimationId::RedDoor
                     } else {
                         AnimationId::BlueDoor
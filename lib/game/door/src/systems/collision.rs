@@ -3,7 +3,7 @@ use amethyst::{
     ecs::{Entities, Join, ReadStorage, System, WriteStorage},
 };
 
-use crate::components::{Door, DoorEntry, DoorState};
+use crate::components::{Door, DoorEntry, DoorState, RoomVisit};
 use hierarchy::components::Child;
 use person::components::{Person, PersonState};
 use physics::components::{Collider, Direction};
@@ -22,6 +22,7 @@ impl<'s> System<'s> for DoorEntryCollisionSystem {
         ReadStorage<'s, Collider>,
         ReadStorage<'s, Direction>,
         ReadStorage<'s, Named>,
+        WriteStorage<'s, RoomVisit>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -35,10 +36,11 @@ impl<'s> System<'s> for DoorEntryCollisionSystem {
             colliders,
             directions,
             names,
+            mut room_visits,
         ) = data;
 
         // check if a player is facing the same direction and is idle
-        for (_, person, _player, player_collider, player_direction, _name) in (
+        for (player_entity, person, _player, player_collider, player_direction, _name) in (
             &entities,
             &mut persons,
             &players,
@@ -59,9 +61,11 @@ impl<'s> System<'s> for DoorEntryCollisionSystem {
                         // get the door
                         for (door_entity, door) in (&entities, &mut doors).join() {
                             if child.parent == door_entity {
-                                if door.state == DoorState::Closed {
+                                if door.state == DoorState::Closed && door.can_user_enter {
                                     door.state = DoorState::Open;
                                     person.state = PersonState::EnteringRoom;
+                                    let _ = room_visits
+                                        .insert(player_entity, RoomVisit::new(door_entity));
                                 }
                                 break;
                             }
@@ -1,9 +1,11 @@
 mod animation;
 mod collision;
+mod room;
 mod spawn;
 mod transformation;
 
 pub use self::animation::DoorAnimationSystem;
 pub use self::collision::DoorEntryCollisionSystem;
+pub use self::room::RoomSystem;
 pub use self::spawn::EnemySpawnSystem;
 pub use self::transformation::DoorTransformationSystem;
@@ -0,0 +1,247 @@
+use amethyst::{
+    core::{math::Vector2, timing::Time, Transform},
+    ecs::{
+        Entities, Entity, Join, LazyUpdate, Read, ReadExpect, ReadStorage, System, Write,
+        WriteStorage,
+    },
+    renderer::Hidden,
+    shrev::EventChannel,
+};
+use rand::Rng;
+
+use crate::components::{Door, DoorEntry, DoorState, RoomVisit};
+use asset::{AssetType, PrefabList};
+use enemy::{components::Enemy, spawn_enemy, EnemyArchetypes, EnemySpawn};
+use floors::Floor;
+use hierarchy::components::Child;
+use level::{CurrentLevel, Difficulty, LevelProgress};
+use person::{
+    components::{Gun, Person, PersonState},
+    Weapons,
+};
+use player::components::Inventory;
+use random::{GameRng, RngStream};
+use score::ScoreEvent;
+
+// how long the walk in and out of the room take, the open door is drawn in front of the person
+const ENTER_SECONDS: f32 = 0.5;
+const EXIT_SECONDS: f32 = 0.5;
+const INSIDE_SECONDS: f32 = 2.;
+// chance that an enemy comes out of a nearby door as the player leaves the room
+const AMBUSH_CHANCE: f64 = 0.5;
+
+/// Takes a person through a room once he enters it: in through the door, hidden inside for a
+/// while to collect the documents or pick up a weapon and back out, after which the door is
+/// used up.
+pub struct RoomSystem;
+
+impl<'s> System<'s> for RoomSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Person>,
+        WriteStorage<'s, RoomVisit>,
+        WriteStorage<'s, Door>,
+        ReadStorage<'s, DoorEntry>,
+        ReadStorage<'s, Child>,
+        WriteStorage<'s, Hidden>,
+        WriteStorage<'s, Inventory>,
+        WriteStorage<'s, Gun>,
+        ReadStorage<'s, Enemy>,
+        ReadStorage<'s, Floor>,
+        ReadStorage<'s, Transform>,
+        Write<'s, LevelProgress>,
+        Write<'s, EventChannel<ScoreEvent>>,
+        Read<'s, Time>,
+        Read<'s, PrefabList>,
+        Read<'s, EnemyArchetypes>,
+        Read<'s, Weapons>,
+        Read<'s, CurrentLevel>,
+        Read<'s, Difficulty>,
+        ReadExpect<'s, LazyUpdate>,
+        Write<'s, GameRng>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            mut persons,
+            mut room_visits,
+            mut doors,
+            door_entries,
+            children,
+            mut hiddens,
+            mut inventories,
+            mut guns,
+            enemies,
+            floors,
+            transforms,
+            mut level_progress,
+            mut score_events,
+            time,
+            prefab_list,
+            archetypes,
+            weapons,
+            current_level,
+            difficulty,
+            lazy_update,
+            mut game_rng,
+        ) = data;
+
+        // an ambush is one more enemy, the same cap as for the ones coming out of doors
+        let mut number_enemies = (&enemies).join().count();
+        let mut finished_visits: Vec<Entity> = Vec::new();
+        for (entity, person, visit) in (&entities, &mut persons, &mut room_visits).join() {
+            // the person was killed on the way in or out, or the door was removed
+            if person.state == PersonState::Dying || !doors.contains(visit.door) {
+                finished_visits.push(entity);
+                continue;
+            }
+            let door = doors.get_mut(visit.door).unwrap();
+            visit.elapsed_seconds += time.delta_seconds();
+            match person.state {
+                PersonState::EnteringRoom if visit.elapsed_seconds >= ENTER_SECONDS => {
+                    person.state = PersonState::InsideRoom;
+                    visit.elapsed_seconds = 0.;
+                    door.state = DoorState::Closed;
+                    set_hidden(&entities, &children, &mut hiddens, entity, true);
+                    if door.has_papers {
+                        door.has_papers = false;
+                        if let Some(inventory) = inventories.get_mut(entity) {
+                            inventory.documents += 1;
+                        }
+                        // the door id is kept when the floor is drawn again
+                        if let Some(floor) = floors.get(visit.door) {
+                            for id in floor.object_ids.iter() {
+                                if level_progress.collect_document(*id) {
+                                    score_events.single_write(ScoreEvent::DocumentsCollected);
+                                }
+                            }
+                        }
+                    }
+                    if let Some(weapon) = door.pickup.as_ref().and_then(|name| weapons.get(name)) {
+                        for (gun, child) in (&mut guns, &children).join() {
+                            if child.parent == entity {
+                                gun.equip(weapon.clone());
+                            }
+                        }
+                        if let Some(floor) = floors.get(visit.door) {
+                            for id in floor.object_ids.iter() {
+                                level_progress.collect_pickup(*id);
+                            }
+                        }
+                        println!("### Picked up the {} ###", weapon.name);
+                    }
+                }
+                PersonState::InsideRoom if visit.elapsed_seconds >= INSIDE_SECONDS => {
+                    person.state = PersonState::ExitingRoom;
+                    visit.elapsed_seconds = 0.;
+                    door.state = DoorState::Open;
+                    set_hidden(&entities, &children, &mut hiddens, entity, false);
+
+                    if number_enemies < difficulty.max_enemies()
+                        && game_rng
+                            .stream(RngStream::EnemySpawn)
+                            .gen_bool(AMBUSH_CHANCE)
+                    {
+                        if let Some(position) = find_ambush_position(
+                            &entities,
+                            &doors,
+                            &floors,
+                            &transforms,
+                            visit.door,
+                        ) {
+                            println!("### Ambush at {:?} ###", position);
+                            number_enemies += 1;
+                            let archetype = archetypes.pick(
+                                current_level.number,
+                                Some(&difficulty.name),
+                                game_rng.stream(RngStream::EnemySpawn),
+                            );
+                            spawn_enemy(
+                                &entities,
+                                &lazy_update,
+                                archetype.prefab_handle(&prefab_list),
+                                prefab_list.get(AssetType::Guns).unwrap().clone(),
+                                EnemySpawn {
+                                    archetype,
+                                    position,
+                                    spawn_time: time.absolute_time_seconds(),
+                                    floors_overlapped: floors
+                                        .get(visit.door)
+                                        .map(|floor| floor.floors_overlapped.clone())
+                                        .unwrap_or_default(),
+                                },
+                            );
+                        }
+                    }
+                }
+                PersonState::ExitingRoom if visit.elapsed_seconds >= EXIT_SECONDS => {
+                    person.state = PersonState::Idling;
+                    door.state = DoorState::Closed;
+                    // the room is used up, the door stays blue and can't be entered again
+                    door.can_user_enter = false;
+                    for (entry_entity, child, _) in (&entities, &children, &door_entries).join() {
+                        if child.parent == visit.door {
+                            let _ = entities.delete(entry_entity);
+                        }
+                    }
+                    finished_visits.push(entity);
+                }
+                _ => {}
+            }
+        }
+        for entity in finished_visits {
+            room_visits.remove(entity);
+        }
+    }
+}
+
+/// Hides or shows a person along with his gun.
+fn set_hidden(
+    entities: &Entities,
+    children: &ReadStorage<Child>,
+    hiddens: &mut WriteStorage<Hidden>,
+    entity: Entity,
+    hidden: bool,
+) {
+    let mut hide = vec![entity];
+    for (child_entity, child) in (entities, children).join() {
+        if child.parent == entity {
+            hide.push(child_entity);
+        }
+    }
+    for entity in hide {
+        if hidden {
+            let _ = hiddens.insert(entity, Hidden);
+        } else {
+            hiddens.remove(entity);
+        }
+    }
+}
+
+/// Where an enemy comes out to ambush, the closest blue door on the same floor as the room.
+fn find_ambush_position(
+    entities: &Entities,
+    doors: &WriteStorage<Door>,
+    floors: &ReadStorage<Floor>,
+    transforms: &ReadStorage<Transform>,
+    room_door: Entity,
+) -> Option<Vector2<f32>> {
+    let room_floor = floors.get(room_door)?;
+    let room_x = transforms.get(room_door)?.translation().x;
+    let mut closest: Option<(f32, Vector2<f32>)> = None;
+    for (entity, door, floor, transform) in (entities, doors, floors, transforms).join() {
+        if entity == room_door
+            || door.can_user_enter
+            || floor.floors_overlapped != room_floor.floors_overlapped
+        {
+            continue;
+        }
+        let translation = transform.translation();
+        let distance = (translation.x - room_x).abs();
+        if closest.map_or(true, |(closest_distance, _)| distance < closest_distance) {
+            closest = Some((distance, Vector2::new(translation.x, translation.y + 12.)));
+        }
+    }
+    closest.map(|(_, position)| position)
+}
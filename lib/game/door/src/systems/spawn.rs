@@ -1,17 +1,15 @@
 use amethyst::{
     core::{math::Vector2, timing::Time, Transform},
-    ecs::{Entities, Join, LazyUpdate, Read, ReadExpect, ReadStorage, System},
+    ecs::{Entities, Join, LazyUpdate, Read, ReadExpect, ReadStorage, System, Write},
 };
 
 use crate::components::Door;
 use asset::{AssetType, PrefabList};
-use enemy::{components::Enemy, spawn_enemy};
+use enemy::{components::Enemy, spawn_enemy, EnemyArchetypes, EnemySpawn};
 use floors::Floor;
+use level::{CurrentLevel, Difficulty};
 use rand::Rng;
-
-const MAX_ENEMIES: usize = 3;
-const ENEMY_FACTOR: f32 = 0.001;
-const TIME_BETWEEN_SPAWNS: f64 = 3.0;
+use random::{GameRng, RngStream};
 
 pub struct EnemySpawnSystem;
 
@@ -23,12 +21,29 @@ impl<'s> System<'s> for EnemySpawnSystem {
         ReadStorage<'s, Floor>,
         ReadStorage<'s, Transform>,
         Read<'s, PrefabList>,
+        Read<'s, EnemyArchetypes>,
+        Read<'s, CurrentLevel>,
+        Read<'s, Difficulty>,
         ReadExpect<'s, LazyUpdate>,
         Read<'s, Time>,
+        Write<'s, GameRng>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (entities, doors, enemies, floors, transforms, prefab_list, lazy_update, time) = data;
+        let (
+            entities,
+            doors,
+            enemies,
+            floors,
+            transforms,
+            prefab_list,
+            archetypes,
+            current_level,
+            difficulty,
+            lazy_update,
+            time,
+            mut game_rng,
+        ) = data;
 
         // logic for adding an enemy
         // 1. count the number of enemies and check if available
@@ -47,33 +62,39 @@ impl<'s> System<'s> for EnemySpawnSystem {
             };
         }
 
-        let mut rng = rand::thread_rng();
         let mut spawned_from_doors: Vec<u32> = Vec::new();
-        if current_time - max_spawn_time < TIME_BETWEEN_SPAWNS {
+        if current_time - max_spawn_time < difficulty.time_between_spawns() {
             return;
         }
+        let max_enemies = difficulty.max_enemies();
+        let rng = game_rng.stream(RngStream::EnemySpawn);
         for (entity, door, floor, transform) in (&entities, &doors, &floors, &transforms).join() {
-            if number_enemies >= MAX_ENEMIES {
+            if number_enemies >= max_enemies {
                 break;
             }
             // bad guys dont come out of red doors
             if !door.can_user_enter {
                 // calculate if we should show an enemy
-                let random_number: i32 = rng.gen_range(0, (1. / ENEMY_FACTOR) as i32);
+                let random_number: i32 = rng.gen_range(0, (1. / difficulty.enemy_factor()) as i32);
                 if random_number == 0 && !spawned_from_doors.contains(&entity.id()) {
                     spawned_from_doors.push(entity.id());
                     number_enemies += 1;
                     let current_translation = transform.translation();
                     let x = current_translation.x;
                     let y = current_translation.y + 12.;
+                    let archetype =
+                        archetypes.pick(current_level.number, Some(&difficulty.name), rng);
                     spawn_enemy(
                         &entities,
                         &lazy_update,
-                        prefab_list.get(AssetType::Enemy).unwrap().clone(),
+                        archetype.prefab_handle(&prefab_list),
                         prefab_list.get(AssetType::Guns).unwrap().clone(),
-                        Vector2::new(x, y),
-                        current_time,
-                        floor.floors_overlapped.clone(),
+                        EnemySpawn {
+                            archetype,
+                            position: Vector2::new(x, y),
+                            spawn_time: current_time,
+                            floors_overlapped: floor.floors_overlapped.clone(),
+                        },
                     );
                 }
             }
@@ -1,8 +1,13 @@
+use std::collections::BTreeSet;
+
 use amethyst::{
     core::math::{Vector2, Vector3},
     ecs::{Component, DenseVecStorage, NullStorage},
 };
 
+// how close to a floor line counts as being on it
+const FLOOR_EPSILON: f32 = 0.01;
+
 #[derive(Component, Default)]
 #[storage(NullStorage)]
 pub struct Rideable;
@@ -52,13 +57,20 @@ pub struct Elevator {
     pub boundaries: Vec<f32>,
     pub floor_height: f32,
     pub num_floors: usize,
+    pub min_floor: usize,
     pub start_floor: usize,
-    pub current_floor: f32,
+    /// the floor it is at or last passed, see `floor_offset` for how far above it the cab is
+    pub current_floor: usize,
+    /// how far (as a fraction of a floor) the cab is above `current_floor`
+    pub floor_offset: f32,
     pub velocity: f32,
     pub previous_state: ElevatorState,
     pub state: ElevatorState,
     pub wait_seconds: f64,
-    pub can_wait: bool,
+    /// floors it has been called to, served in the order it passes them
+    pub requests: BTreeSet<usize>,
+    /// the player is inside and in control
+    pub is_occupied: bool,
 }
 
 impl Default for Elevator {
@@ -68,13 +80,16 @@ impl Default for Elevator {
             boundaries: Vec::new(),
             floor_height: 48.,
             num_floors: 1,
+            min_floor: 0,
             start_floor: 0,
-            current_floor: 0.,
+            current_floor: 0,
+            floor_offset: 0.,
             velocity: 0.,
             previous_state: ElevatorState::Waiting,
             state: ElevatorState::Waiting,
             wait_seconds: 0.,
-            can_wait: true,
+            requests: BTreeSet::new(),
+            is_occupied: false,
         }
     }
 }
@@ -99,10 +114,238 @@ impl Elevator {
             position,
             boundaries,
             num_floors: max_floor - min_floor + 1,
+            min_floor,
             start_floor,
-            current_floor: start_floor as f32,
+            current_floor: start_floor,
             velocity,
             ..Elevator::default()
         }
     }
+
+    pub fn max_floor(&self) -> usize {
+        self.min_floor + self.num_floors - 1
+    }
+
+    /// The floor it is closest to.
+    pub fn floor(&self) -> usize {
+        if self.floor_offset >= 0.5 {
+            self.current_floor + 1
+        } else {
+            self.current_floor
+        }
+    }
+
+    /// The floor number with the fraction of the way to the next one.
+    pub fn exact_floor(&self) -> f32 {
+        self.current_floor as f32 + self.floor_offset
+    }
+
+    pub fn is_at_bottom(&self) -> bool {
+        self.boundaries
+            .first()
+            .map_or(true, |bottom| self.position.y <= *bottom)
+    }
+
+    pub fn is_at_top(&self) -> bool {
+        self.boundaries
+            .last()
+            .map_or(true, |top| self.position.y >= *top)
+    }
+
+    /// Moves the cab along the shaft, never past the top or bottom floor. It stops on the
+    /// first floor line it reaches that it should stop at, returns whether it stopped.
+    pub fn move_by(&mut self, distance: f32, current_time: f64) -> bool {
+        let (bottom, top) = match (self.boundaries.first(), self.boundaries.last()) {
+            (Some(bottom), Some(top)) => (*bottom, *top),
+            _ => return false,
+        };
+        let old_y = self.position.y;
+        let new_y = (old_y + distance).max(bottom).min(top);
+        let last = self.boundaries.len() - 1;
+        let mut crossed: Vec<usize> = (0..=last)
+            .filter(|i| {
+                let boundary = self.boundaries[*i];
+                if distance > 0. {
+                    old_y < boundary && boundary <= new_y
+                } else {
+                    new_y <= boundary && boundary < old_y
+                }
+            })
+            .collect();
+        if distance < 0. {
+            crossed.reverse();
+        }
+        let stop_index = crossed
+            .into_iter()
+            .find(|i| *i == 0 || *i == last || self.should_stop_at(self.min_floor + *i));
+        match stop_index {
+            Some(index) => {
+                self.position.y = self.boundaries[index];
+                self.stop(self.min_floor + index, current_time);
+            }
+            None => self.position.y = new_y,
+        }
+        self.update_floor();
+        stop_index.is_some()
+    }
+
+    fn stop(&mut self, floor: usize, current_time: f64) {
+        self.velocity = 0.;
+        self.previous_state = self.state;
+        self.state = ElevatorState::Waiting;
+        self.wait_seconds = current_time;
+        self.requests.remove(&floor);
+    }
+
+    fn update_floor(&mut self) {
+        let y = self.position.y;
+        let index = self
+            .boundaries
+            .iter()
+            .rposition(|boundary| *boundary <= y + FLOOR_EPSILON)
+            .unwrap_or(0);
+        self.current_floor = self.min_floor + index;
+        self.floor_offset = self
+            .boundaries
+            .get(index)
+            .map_or(0., |boundary| ((y - boundary) / self.floor_height).max(0.));
+        if self.floor_offset * self.floor_height < FLOOR_EPSILON {
+            self.floor_offset = 0.;
+        }
+    }
+
+    /// Call the elevator to a floor, floors it doesn't reach are ignored.
+    pub fn request(&mut self, floor: usize) {
+        if floor >= self.min_floor && floor <= self.max_floor() && self.requests.insert(floor) {
+            println!("### Elevator called to floor {} ###", floor);
+        }
+    }
+
+    /// Without any calls (or with the player riding) it stops at every floor.
+    pub fn should_stop_at(&self, floor: usize) -> bool {
+        self.is_occupied || self.requests.is_empty() || self.requests.contains(&floor)
+    }
+
+    /// With nothing to do it keeps going up and down one floor at a time.
+    pub fn patrol(&mut self) {
+        if self.num_floors < 2 {
+            return;
+        }
+        let floor = self.floor();
+        let going_up = if self.previous_state == ElevatorState::Up {
+            floor < self.max_floor()
+        } else {
+            floor <= self.min_floor
+        };
+        self.request(if going_up { floor + 1 } else { floor - 1 });
+    }
+
+    /// Keep going the same way while there are calls ahead, then turn around (LOOK).
+    pub fn next_state(&self) -> ElevatorState {
+        let floor = self.floor();
+        let above = self.requests.iter().any(|f| *f > floor);
+        let below = self.requests.iter().any(|f| *f < floor);
+        match self.previous_state {
+            ElevatorState::Down if below => ElevatorState::Down,
+            _ if above => ElevatorState::Up,
+            _ if below => ElevatorState::Down,
+            _ => ElevatorState::Waiting,
+        }
+    }
+}
+
+/// Someone pressing the button at an elevator shaft, or a floor button inside the cab.
+#[derive(Component, Default)]
+#[storage(DenseVecStorage)]
+pub struct ElevatorCall {
+    pub up: bool,
+    pub down: bool,
+    /// the floor to go to, when inside the cab
+    pub floor: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Floors 0 to 4, 48 pixels apart, with the cab waiting at `floor`.
+    fn elevator(floor: usize) -> Elevator {
+        Elevator::new(Vector2::new(0., floor as f32 * 48.), 0, 4, floor, 0.)
+    }
+
+    #[test]
+    fn ignores_calls_to_floors_it_does_not_reach() {
+        let mut elevator = Elevator::new(Vector2::new(0., 0.), 1, 3, 1, 0.);
+        elevator.request(0);
+        elevator.request(2);
+        elevator.request(2);
+        elevator.request(4);
+        assert_eq!(
+            elevator.requests.iter().copied().collect::<Vec<usize>>(),
+            vec![2]
+        );
+    }
+
+    #[test]
+    fn keeps_going_while_there_are_calls_ahead() {
+        let mut elevator = elevator(2);
+        elevator.request(0);
+        elevator.request(4);
+
+        elevator.previous_state = ElevatorState::Up;
+        assert_eq!(elevator.next_state(), ElevatorState::Up);
+        elevator.previous_state = ElevatorState::Down;
+        assert_eq!(elevator.next_state(), ElevatorState::Down);
+
+        elevator.requests.remove(&0);
+        assert_eq!(elevator.next_state(), ElevatorState::Up);
+        elevator.requests.clear();
+        assert_eq!(elevator.next_state(), ElevatorState::Waiting);
+    }
+
+    #[test]
+    fn only_stops_at_called_floors() {
+        let mut elevator = elevator(0);
+        elevator.request(3);
+        elevator.state = ElevatorState::Up;
+        assert!(!elevator.move_by(100., 0.));
+        assert_eq!(elevator.position.y, 100.);
+        assert!(elevator.move_by(100., 1.));
+        assert_eq!(elevator.position.y, 144.);
+        assert_eq!((elevator.current_floor, elevator.floor_offset), (3, 0.));
+        assert_eq!(elevator.state, ElevatorState::Waiting);
+        assert_eq!(elevator.previous_state, ElevatorState::Up);
+        assert!(elevator.requests.is_empty());
+    }
+
+    #[test]
+    fn serves_calls_in_the_order_it_passes_them() {
+        let mut elevator = elevator(2);
+        elevator.previous_state = ElevatorState::Up;
+        elevator.request(1);
+        elevator.request(4);
+        let mut served: Vec<usize> = Vec::new();
+        while !elevator.requests.is_empty() {
+            elevator.state = elevator.next_state();
+            let distance = match elevator.state {
+                ElevatorState::Up => 12.,
+                ElevatorState::Down => -12.,
+                ElevatorState::Waiting => break,
+            };
+            if elevator.move_by(distance, 0.) {
+                served.push(elevator.floor());
+            }
+        }
+        assert_eq!(served, vec![4, 1]);
+    }
+
+    #[test]
+    fn patrols_one_floor_at_a_time() {
+        let mut elevator = elevator(0);
+        elevator.patrol();
+        assert_eq!(
+            elevator.requests.iter().copied().collect::<Vec<usize>>(),
+            vec![1]
+        );
+    }
 }
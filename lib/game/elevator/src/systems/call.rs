@@ -0,0 +1,87 @@
+use amethyst::core::Named;
+use amethyst::ecs::{Join, ReadStorage, System, WriteStorage};
+
+use crate::components::{Elevator, ElevatorCall};
+use hierarchy::components::Child;
+use physics::components::Collider;
+
+// how far from the middle of the shaft someone can be to press the button
+const CALL_DISTANCE: f32 = 26.0;
+// the floor of the cab is this far below its boundary when it's stopped at a floor
+const CAB_FLOOR_OFFSET: f32 = 22.0;
+const FLOOR_TOLERANCE: f32 = 6.0;
+
+/// Turns the call buttons pressed by persons into requests for the elevators.
+pub struct ElevatorCallSystem;
+
+impl<'s> System<'s> for ElevatorCallSystem {
+    type SystemData = (
+        WriteStorage<'s, Elevator>,
+        ReadStorage<'s, ElevatorCall>,
+        ReadStorage<'s, Collider>,
+        ReadStorage<'s, Child>,
+        ReadStorage<'s, Named>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (mut elevators, calls, colliders, children, names) = data;
+
+        for (call, collider, named) in (&calls, &colliders, &names).join() {
+            if !call.up && !call.down && call.floor.is_none() {
+                continue;
+            }
+            let bbox = &collider.bounding_box;
+
+            // inside a cab, the buttons pick a floor or the next floor that way
+            // (the player drives the cab directly instead)
+            if collider.on_elevator {
+                let cab = collider
+                    .riding
+                    .filter(|riding| {
+                        names
+                            .get(*riding)
+                            .map_or(false, |name| name.name == "ElevatorBottom")
+                    })
+                    .and_then(|riding| children.get(riding))
+                    .map(|child| child.parent);
+                let elevator = cab.and_then(|cab| elevators.get_mut(cab));
+                if let (Some(elevator), false) = (elevator, named.name == "Player") {
+                    let floor = elevator.floor();
+                    if let Some(to_floor) = call.floor {
+                        if elevator.velocity != 0. || floor != to_floor {
+                            elevator.request(to_floor);
+                        }
+                    } else if elevator.requests.is_empty() {
+                        if call.up {
+                            elevator.request(floor + 1);
+                        } else if floor > 0 {
+                            elevator.request(floor - 1);
+                        }
+                    }
+                }
+                continue;
+            }
+
+            // standing next to a shaft, call it to this floor
+            if !collider.on_ground {
+                continue;
+            }
+            let feet = bbox.position.y - bbox.half_size.y;
+            for elevator in (&mut elevators).join() {
+                if (bbox.position.x - elevator.position.x).abs() > CALL_DISTANCE {
+                    continue;
+                }
+                let index = elevator.boundaries.iter().position(|boundary| {
+                    (boundary - CAB_FLOOR_OFFSET - feet).abs() <= FLOOR_TOLERANCE
+                });
+                if let Some(index) = index {
+                    let floor = elevator.min_floor + index;
+                    // no need if it's already waiting here
+                    if elevator.velocity != 0. || elevator.floor() != floor {
+                        elevator.request(floor);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use amethyst::{
+    core::Named,
+    ecs::{Entities, Join, ReadStorage, System, Write, WriteStorage},
+    shrev::EventChannel,
+};
+
+use crate::components::{Elevator, ElevatorComponent};
+use hierarchy::components::Child;
+use person::components::{Person, PersonState};
+use physics::components::{Collider, Motion};
+use score::{Bounty, KillCause, ScoreEvent};
+
+// the underside of the floor above the top stop is this far above the top boundary
+const CEILING_OFFSET: f32 = 24.0;
+// brushing the edge of the cab isn't enough to get crushed
+const EDGE_PADDING: f32 = 2.0;
+
+/// Kills persons caught under a cab on its way down, or on its roof when it goes up into the
+/// top of the shaft.
+pub struct ElevatorCrushSystem;
+
+impl<'s> System<'s> for ElevatorCrushSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Elevator>,
+        ReadStorage<'s, ElevatorComponent>,
+        ReadStorage<'s, Child>,
+        ReadStorage<'s, Named>,
+        WriteStorage<'s, Person>,
+        ReadStorage<'s, Collider>,
+        WriteStorage<'s, Motion>,
+        ReadStorage<'s, Bounty>,
+        Write<'s, EventChannel<ScoreEvent>>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            elevators,
+            components,
+            children,
+            names,
+            mut persons,
+            colliders,
+            mut motions,
+            bounties,
+            mut score_events,
+        ) = data;
+
+        for (part_entity, _component, child, part_named, part_collider) in
+            (&entities, &components, &children, &names, &colliders).join()
+        {
+            let elevator = match elevators.get(child.parent) {
+                Some(elevator) if elevator.velocity != 0. => elevator,
+                _ => continue,
+            };
+            let part = &part_collider.bounding_box;
+            let is_bottom = part_named.name == "ElevatorBottom";
+            let is_top = part_named.name == "ElevatorTop";
+            if !(is_bottom && elevator.velocity < 0.) && !(is_top && elevator.velocity > 0.) {
+                continue;
+            }
+            let ceiling = elevator.boundaries.last().cloned().unwrap_or(0.) + CEILING_OFFSET;
+
+            for (person_entity, person, collider, motion, named) in
+                (&entities, &mut persons, &colliders, &mut motions, &names).join()
+            {
+                if person.state == PersonState::Dying || person.state.is_in_room() {
+                    continue;
+                }
+                let bbox = &collider.bounding_box;
+                let overlaps_x = (bbox.position.x - part.position.x).abs()
+                    < bbox.half_size.x + part.half_size.x - EDGE_PADDING;
+                let head = bbox.position.y + bbox.half_size.y;
+                let crushed = overlaps_x
+                    && if is_bottom {
+                        // underneath the cab with no room left
+                        bbox.position.y < part.position.y
+                            && head >= part.position.y - part.half_size.y
+                    } else {
+                        // riding the roof up into the ceiling
+                        collider.riding == Some(part_entity) && head > ceiling
+                    };
+                if crushed {
+                    println!("### {} crushed by an elevator ###", named.name);
+                    if named.name == "Enemy" {
+                        score_events.single_write(ScoreEvent::enemy_killed(
+                            KillCause::Elevator,
+                            bounties.get(person_entity),
+                        ));
+                    }
+                    person.kill(KillCause::Elevator);
+                    motion.velocity.x = 0.;
+                }
+            }
+        }
+    }
+}
@@ -1,15 +1,15 @@
 use amethyst::core::timing::Time;
-use amethyst::core::SystemDesc;
+use amethyst::core::{Named, SystemDesc};
 use amethyst::derive::SystemDesc;
-use amethyst::ecs::{Entities, Join, Read, ReadStorage, System, SystemData, World, WriteStorage};
-use amethyst::input::{InputHandler, StringBindings};
+use amethyst::ecs::{
+    Entities, Entity, Join, Read, ReadStorage, System, SystemData, World, WriteStorage,
+};
 
 use crate::components::{Elevator, ElevatorComponent, ElevatorState};
+use controls::ControlInput;
 use hierarchy::components::Child;
-use physics::components::Motion;
-
-const WAIT_TIME: f64 = 2.2;
-const VELOCITY: f32 = 20.0;
+use level::Difficulty;
+use physics::components::{Collider, Motion};
 
 #[derive(SystemDesc)]
 pub struct ElevatorControlSystem;
@@ -21,52 +21,81 @@ impl<'s> System<'s> for ElevatorControlSystem {
         ReadStorage<'s, ElevatorComponent>,
         ReadStorage<'s, Child>,
         WriteStorage<'s, Motion>,
-        Read<'s, InputHandler<StringBindings>>,
+        ReadStorage<'s, Collider>,
+        ReadStorage<'s, Named>,
+        Read<'s, ControlInput>,
+        Read<'s, Difficulty>,
         Read<'s, Time>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (entities, mut elevators, components, children, mut motions, input, time) = data;
-        let up_input = input.action_is_down("up").expect("Up action exists");
-        let down_input = input.action_is_down("down").expect("Down action exists");
+        let (
+            entities,
+            mut elevators,
+            components,
+            children,
+            mut motions,
+            colliders,
+            names,
+            input,
+            difficulty,
+            time,
+        ) = data;
+        let up_input = input.up;
+        let down_input = input.down;
+
+        // the elevator the player is standing inside of (not on the roof)
+        let mut occupied: Option<Entity> = None;
+        for (collider, named) in (&colliders, &names).join() {
+            if named.name == "Player" && collider.on_elevator {
+                occupied = collider
+                    .riding
+                    .filter(|riding| {
+                        names
+                            .get(*riding)
+                            .map_or(false, |name| name.name == "ElevatorBottom")
+                    })
+                    .and_then(|riding| children.get(riding))
+                    .map(|child| child.parent);
+            }
+        }
+
         for (entity, elevator) in (&entities, &mut elevators).join() {
             let current_time: f64 = time.absolute_time_seconds();
-            if current_time - elevator.wait_seconds > WAIT_TIME
-                && elevator.state == ElevatorState::Waiting
-            {
-                // set it to the last known state
-                elevator.state = if elevator.previous_state == ElevatorState::Waiting {
-                    ElevatorState::Down
-                } else {
-                    elevator.previous_state
-                };
-
-                // check for boundaries
-                if elevator.state == ElevatorState::Up
-                    && elevator.current_floor
-                        == (elevator.start_floor + elevator.num_floors - 1) as f32
-                {
+            elevator.is_occupied = occupied == Some(entity);
+            if elevator.is_occupied {
+                // the player is in control, it only moves when asked to
+                if down_input && !elevator.is_at_bottom() {
                     elevator.state = ElevatorState::Down;
-                } else if elevator.state == ElevatorState::Down
-                    && elevator.current_floor == elevator.start_floor as f32
-                {
+                } else if up_input && !elevator.is_at_top() {
                     elevator.state = ElevatorState::Up;
+                } else if elevator.state == ElevatorState::Waiting {
+                    // start the usual wait over once the player gets out
+                    elevator.wait_seconds = current_time;
                 }
-            } else if down_input
-                && (elevator.current_floor > elevator.start_floor as f32 || elevator.velocity > 0.)
-            {
-                elevator.state = ElevatorState::Down;
-            } else if up_input
-                && elevator.current_floor < (elevator.start_floor + elevator.num_floors) as f32
+            } else if current_time - elevator.wait_seconds > difficulty.settings.elevator_wait_time
+                && elevator.state == ElevatorState::Waiting
             {
-                elevator.state = ElevatorState::Up;
+                let floor = elevator.floor();
+                if elevator.requests.remove(&floor) {
+                    // called to where it already is, wait here again
+                    elevator.wait_seconds = current_time;
+                } else {
+                    if elevator.requests.is_empty() {
+                        elevator.patrol();
+                    }
+                    elevator.state = elevator.next_state();
+                }
             }
 
             elevator.velocity = match elevator.state {
-                ElevatorState::Up => VELOCITY,
-                ElevatorState::Down => -VELOCITY,
+                ElevatorState::Up => difficulty.settings.elevator_velocity,
+                ElevatorState::Down => -difficulty.settings.elevator_velocity,
                 _ => 0.,
             };
+            if elevator.velocity != 0. {
+                elevator.move_by(elevator.velocity * time.delta_seconds(), current_time);
+            }
 
             // loop through components and set their velocities
             for (_components, child, motion) in (&components, &children, &mut motions).join() {
@@ -1,5 +1,9 @@
+mod call;
+mod crush;
 mod elevator;
 mod transformation;
 
+pub use self::call::ElevatorCallSystem;
+pub use self::crush::ElevatorCrushSystem;
 pub use self::elevator::ElevatorControlSystem;
 pub use self::transformation::ElevatorTransformationSystem;
@@ -0,0 +1,301 @@
+use std::{fs::File, path::Path};
+
+use amethyst::{
+    assets::{Handle, Prefab},
+    error::Error,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::components::Tactic;
+use animation::components::AnimationPrefabData;
+use asset::{AssetType, PrefabList};
+
+/// A kind of enemy, like a regular agent or a sniper.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct EnemyArchetype {
+    pub name: String,
+    /// path of the animation prefab, relative to the assets directory
+    pub prefab: String,
+    pub max_ground_speed: f32,
+    pub max_jump_velocity: f32,
+    /// seconds between seeing the player and the first shot
+    pub reaction_time: f64,
+    /// chance of a shot being aimed at the player instead of over his head
+    pub accuracy: f32,
+    pub bullet_velocity: f32,
+    /// the tactics he picks from once he sees the player
+    pub tactics: Vec<Tactic>,
+    /// points for shooting him
+    pub score: u32,
+    /// name of his tree in `assets/behaviours.ron`, the built-in tree is used when there's none
+    #[serde(default)]
+    pub behaviour: String,
+}
+
+impl Default for EnemyArchetype {
+    fn default() -> Self {
+        EnemyArchetype {
+            name: String::from("agent"),
+            prefab: String::from("prefabs/enemy.ron"),
+            max_ground_speed: 36.,
+            max_jump_velocity: 110.,
+            reaction_time: 0.5,
+            accuracy: 0.8,
+            bullet_velocity: 200.,
+            tactics: vec![Tactic::Advance, Tactic::Hold, Tactic::Retreat],
+            score: 100,
+            behaviour: String::new(),
+        }
+    }
+}
+
+/// How often each archetype comes out of a door.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SpawnChance {
+    pub archetype: String,
+    pub weight: u32,
+}
+
+/// The enemies of the levels from `from_level` on, until a table with a higher `from_level`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SpawnTable {
+    pub from_level: usize,
+    /// only used on this difficulty, or on any difficulty without a table of its own when `None`
+    #[serde(default)]
+    pub difficulty: Option<String>,
+    pub enemies: Vec<SpawnChance>,
+}
+
+/// All the kinds of enemies and which ones appear where, loaded from `assets/enemies.ron`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct EnemyArchetypes {
+    pub archetypes: Vec<EnemyArchetype>,
+    pub spawn_tables: Vec<SpawnTable>,
+}
+
+impl Default for EnemyArchetypes {
+    fn default() -> Self {
+        EnemyArchetypes {
+            archetypes: vec![EnemyArchetype::default()],
+            spawn_tables: Vec::new(),
+        }
+    }
+}
+
+impl EnemyArchetype {
+    /// The loaded prefab, or the regular enemy one when it wasn't loaded.
+    pub fn prefab_handle(&self, prefab_list: &PrefabList) -> Handle<Prefab<AnimationPrefabData>> {
+        prefab_list
+            .get_path(&self.prefab)
+            .or_else(|| prefab_list.get(AssetType::Enemy))
+            .unwrap()
+            .clone()
+    }
+}
+
+impl EnemyArchetypes {
+    pub fn load(path: &Path) -> Result<EnemyArchetypes, Error> {
+        let file = File::open(path)?;
+        let archetypes: EnemyArchetypes = ron::de::from_reader(file)?;
+        archetypes.check(path)?;
+        Ok(archetypes)
+    }
+
+    fn check(&self, path: &Path) -> Result<(), Error> {
+        if self.archetypes.is_empty() {
+            return Err(Error::from_string(format!("No enemies in {:?}", path)));
+        }
+        for archetype in self.archetypes.iter() {
+            let problem = if !(0. ..=1.).contains(&archetype.accuracy) {
+                Some("an accuracy from 0 to 1")
+            } else if archetype.bullet_velocity.is_nan() || archetype.bullet_velocity <= 0. {
+                Some("a bullet velocity above 0")
+            } else {
+                None
+            };
+            if let Some(problem) = problem {
+                return Err(Error::from_string(format!(
+                    "Enemy {} in {:?} needs {}",
+                    archetype.name, path, problem
+                )));
+            }
+        }
+        for table in self.spawn_tables.iter() {
+            for chance in table.enemies.iter() {
+                if self.get(&chance.archetype).is_none() {
+                    return Err(Error::from_string(format!(
+                        "Unknown enemy {} in {:?}",
+                        chance.archetype, path
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&EnemyArchetype> {
+        self.archetypes
+            .iter()
+            .find(|archetype| archetype.name == name)
+    }
+
+    /// The prefabs of all the archetypes, to be loaded with the other assets.
+    pub fn prefabs(&self) -> Vec<String> {
+        let mut prefabs: Vec<String> = Vec::new();
+        for archetype in self.archetypes.iter() {
+            if !prefabs.contains(&archetype.prefab) {
+                prefabs.push(archetype.prefab.clone());
+            }
+        }
+        prefabs
+    }
+
+    /// The spawn table for a level, a table for the difficulty wins over one for any difficulty.
+    pub fn spawn_table(&self, level: usize, difficulty: Option<&str>) -> Option<&SpawnTable> {
+        let tables = self
+            .spawn_tables
+            .iter()
+            .filter(|table| table.from_level <= level);
+        let for_difficulty = tables
+            .clone()
+            .filter(|table| {
+                difficulty.is_some() && table.difficulty.as_ref().map(String::as_str) == difficulty
+            })
+            .max_by_key(|table| table.from_level);
+        for_difficulty.or_else(|| {
+            tables
+                .filter(|table| table.difficulty.is_none())
+                .max_by_key(|table| table.from_level)
+        })
+    }
+
+    /// Picks the archetype of the next enemy, the first archetype when there's no spawn table.
+    pub fn pick<R: Rng>(
+        &self,
+        level: usize,
+        difficulty: Option<&str>,
+        rng: &mut R,
+    ) -> &EnemyArchetype {
+        let first = &self.archetypes[0];
+        let table = match self.spawn_table(level, difficulty) {
+            Some(table) => table,
+            None => return first,
+        };
+        let total: u32 = table.enemies.iter().map(|chance| chance.weight).sum();
+        if total == 0 {
+            return first;
+        }
+        let mut roll = rng.gen_range(0, total);
+        for chance in table.enemies.iter() {
+            if roll < chance.weight {
+                return self.get(&chance.archetype).unwrap_or(first);
+            }
+            roll -= chance.weight;
+        }
+        first
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::mock::StepRng;
+
+    fn archetype(name: &str) -> EnemyArchetype {
+        EnemyArchetype {
+            name: name.to_string(),
+            ..EnemyArchetype::default()
+        }
+    }
+
+    fn table(from_level: usize, difficulty: Option<&str>, enemies: &[(&str, u32)]) -> SpawnTable {
+        SpawnTable {
+            from_level,
+            difficulty: difficulty.map(String::from),
+            enemies: enemies
+                .iter()
+                .map(|(archetype, weight)| SpawnChance {
+                    archetype: archetype.to_string(),
+                    weight: *weight,
+                })
+                .collect(),
+        }
+    }
+
+    fn archetypes(spawn_tables: Vec<SpawnTable>) -> EnemyArchetypes {
+        EnemyArchetypes {
+            archetypes: vec![archetype("agent"), archetype("runner"), archetype("sniper")],
+            spawn_tables,
+        }
+    }
+
+    #[test]
+    fn uses_the_latest_table_that_has_started() {
+        let archetypes = archetypes(vec![
+            table(0, None, &[("agent", 1)]),
+            table(4, None, &[("sniper", 1)]),
+            table(2, None, &[("runner", 1)]),
+        ]);
+        let from_level = |level| archetypes.spawn_table(level, None).unwrap().from_level;
+        assert_eq!(from_level(0), 0);
+        assert_eq!(from_level(1), 0);
+        assert_eq!(from_level(2), 2);
+        assert_eq!(from_level(3), 2);
+        assert_eq!(from_level(9), 4);
+        assert_eq!(
+            archetypes.pick(3, None, &mut StepRng::new(0, 1)).name,
+            "runner"
+        );
+    }
+
+    #[test]
+    fn prefers_a_table_for_the_difficulty() {
+        let archetypes = archetypes(vec![
+            table(0, None, &[("agent", 1)]),
+            table(2, None, &[("runner", 1)]),
+            table(1, Some("arcade"), &[("sniper", 1)]),
+        ]);
+        let picked = |level, difficulty| {
+            archetypes
+                .pick(level, difficulty, &mut StepRng::new(0, 1))
+                .name
+                .clone()
+        };
+        assert_eq!(picked(0, Some("arcade")), "agent");
+        assert_eq!(picked(3, Some("arcade")), "sniper");
+        assert_eq!(picked(3, Some("easy")), "runner");
+        assert_eq!(picked(3, None), "runner");
+    }
+
+    #[test]
+    fn falls_back_to_the_first_archetype() {
+        let mut rng = StepRng::new(0, 1);
+        assert_eq!(archetypes(Vec::new()).pick(0, None, &mut rng).name, "agent");
+        let no_weight = archetypes(vec![table(0, None, &[("sniper", 0), ("runner", 0)])]);
+        assert_eq!(no_weight.pick(0, None, &mut rng).name, "agent");
+    }
+
+    #[test]
+    fn rejects_unknown_archetypes_and_bad_values() {
+        let path = Path::new("enemies.ron");
+        assert!(archetypes(vec![table(0, None, &[("runner", 1)])])
+            .check(path)
+            .is_ok());
+        assert!(archetypes(vec![table(0, None, &[("ninja", 1)])])
+            .check(path)
+            .is_err());
+
+        let mut inaccurate = archetypes(Vec::new());
+        inaccurate.archetypes[1].accuracy = 1.5;
+        assert!(inaccurate.check(path).is_err());
+        let mut slow = archetypes(Vec::new());
+        slow.archetypes[2].bullet_velocity = 0.;
+        assert!(slow.check(path).is_err());
+        let none = EnemyArchetypes {
+            archetypes: Vec::new(),
+            spawn_tables: Vec::new(),
+        };
+        assert!(none.check(path).is_err());
+    }
+}
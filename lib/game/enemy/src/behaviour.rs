@@ -0,0 +1,452 @@
+use std::{collections::HashMap, fs::File, path::Path};
+
+use amethyst::{ecs::Entity, error::Error};
+use serde::{Deserialize, Serialize};
+
+use crate::{components::Tactic, EnemyArchetypes};
+use person::components::PersonState;
+
+/// What an enemy knows about his situation, worked out every frame, and what he remembers
+/// from one frame to the next.
+#[derive(Clone, Debug, Default)]
+pub struct Blackboard {
+    pub sees_player: bool,
+    /// his gun is ready, he has had time to react and there's a shot left in the fire budget
+    pub can_shoot: bool,
+    pub on_elevator: bool,
+    /// he noticed a bullet about to hit him
+    pub bullet_incoming: bool,
+    pub near_door: bool,
+    pub pursuing: bool,
+
+    pub tactic: Option<Tactic>,
+    pub tactic_time: f64,
+    /// when he first saw the player, he needs a moment before shooting
+    pub sighted_time: Option<f64>,
+    /// ducking or jumping out of the way of a bullet
+    pub dodge: Option<PersonState>,
+    pub dodge_time: f64,
+    /// the last bullet he saw coming, he only gets one chance to notice each bullet
+    pub seen_bullet: Option<Entity>,
+    /// the bullet he last got out of the way of, he doesn't dodge the same one twice
+    pub dodged_bullet: Option<Entity>,
+
+    /// what the tree decided last frame and the nodes that led to it
+    pub action: Option<Action>,
+    pub branch: Vec<String>,
+}
+
+impl Blackboard {
+    /// Forgets about the fight, when he dies or gets on an escalator.
+    pub fn reset_combat(&mut self) {
+        self.tactic = None;
+        self.sighted_time = None;
+        self.dodge = None;
+    }
+
+    /// The facts and the active branch on one line, for debugging.
+    pub fn dump(&self) -> String {
+        let facts = [
+            ("sees_player", self.sees_player),
+            ("can_shoot", self.can_shoot),
+            ("on_elevator", self.on_elevator),
+            ("bullet_incoming", self.bullet_incoming),
+            ("near_door", self.near_door),
+            ("pursuing", self.pursuing),
+        ];
+        let known: Vec<&str> = facts
+            .iter()
+            .filter(|(_name, known)| *known)
+            .map(|(name, _known)| *name)
+            .collect();
+        format!("[{}] {}", known.join(", "), self.branch.join(" > "))
+    }
+}
+
+/// Something that can be checked on the blackboard.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Condition {
+    SeesPlayer,
+    CanShoot,
+    OnElevator,
+    BulletIncoming,
+    NearDoor,
+    Pursuing,
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    pub fn holds(&self, blackboard: &Blackboard) -> bool {
+        match self {
+            Condition::SeesPlayer => blackboard.sees_player,
+            Condition::CanShoot => blackboard.can_shoot,
+            Condition::OnElevator => blackboard.on_elevator,
+            Condition::BulletIncoming => blackboard.bullet_incoming,
+            Condition::NearDoor => blackboard.near_door,
+            Condition::Pursuing => blackboard.pursuing,
+            Condition::Not(condition) => !condition.holds(blackboard),
+        }
+    }
+}
+
+/// What an enemy does this frame.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Action {
+    /// duck or jump out of the way of a bullet
+    Dodge,
+    /// one of the archetype's tactics, picked again every couple of seconds
+    Fight,
+    Advance,
+    Hold,
+    Retreat,
+    /// leave it to the pursuit and navigation of the `EnemyAISystem`
+    Pursue,
+    Idle,
+}
+
+/// An option of a `Utility` node, its score is `base` plus the bonus of every condition that
+/// holds.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Consideration {
+    pub base: f32,
+    #[serde(default)]
+    pub bonuses: Vec<(Condition, f32)>,
+    pub node: Node,
+}
+
+impl Consideration {
+    pub fn score(&self, blackboard: &Blackboard) -> f32 {
+        self.bonuses
+            .iter()
+            .filter(|(condition, _bonus)| condition.holds(blackboard))
+            .fold(self.base, |score, (_condition, bonus)| score + bonus)
+    }
+}
+
+/// A node of a behaviour tree.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Node {
+    /// the first child that succeeds
+    Selector(Vec<Node>),
+    /// all the children in order, failing at the first one that fails
+    Sequence(Vec<Node>),
+    /// the first child that succeeds, trying the best scoring one first
+    Utility(Vec<Consideration>),
+    /// succeeds when the condition holds
+    If(Condition),
+    Do(Action),
+}
+
+impl Node {
+    /// The tree used by archetypes without one of their own, and when a tree can't be found.
+    pub fn fallback() -> Node {
+        Node::Selector(vec![
+            Node::Sequence(vec![
+                Node::If(Condition::BulletIncoming),
+                Node::Do(Action::Dodge),
+            ]),
+            Node::Sequence(vec![
+                Node::If(Condition::SeesPlayer),
+                Node::Do(Action::Fight),
+            ]),
+            Node::Do(Action::Pursue),
+        ])
+    }
+
+    /// Runs the tree, returns the action it decided on and fills `branch` with the nodes that
+    /// led to it.
+    pub fn decide(&self, blackboard: &Blackboard, branch: &mut Vec<String>) -> Option<Action> {
+        branch.clear();
+        match self.evaluate(blackboard, branch) {
+            Outcome::Success(action) => action,
+            Outcome::Failure => None,
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Node::Selector(_) => String::from("Selector"),
+            Node::Sequence(_) => String::from("Sequence"),
+            Node::Utility(_) => String::from("Utility"),
+            Node::If(condition) => format!("If({:?})", condition),
+            Node::Do(action) => format!("Do({:?})", action),
+        }
+    }
+
+    fn evaluate(&self, blackboard: &Blackboard, branch: &mut Vec<String>) -> Outcome {
+        let depth = branch.len();
+        branch.push(self.label());
+        let outcome = match self {
+            Node::Selector(children) => children
+                .iter()
+                .map(|child| child.evaluate(blackboard, branch))
+                .find(|outcome| *outcome != Outcome::Failure)
+                .unwrap_or(Outcome::Failure),
+            Node::Sequence(children) => {
+                let mut outcome = Outcome::Success(None);
+                for child in children.iter() {
+                    match child.evaluate(blackboard, branch) {
+                        Outcome::Failure => {
+                            outcome = Outcome::Failure;
+                            break;
+                        }
+                        Outcome::Success(Some(action)) => outcome = Outcome::Success(Some(action)),
+                        Outcome::Success(None) => {}
+                    }
+                }
+                outcome
+            }
+            Node::Utility(considerations) => {
+                let mut scored: Vec<(f32, &Consideration)> = considerations
+                    .iter()
+                    .map(|consideration| (consideration.score(blackboard), consideration))
+                    .collect();
+                // scores that aren't numbers go last
+                scored.sort_by(|(a, _), (b, _)| {
+                    b.partial_cmp(a)
+                        .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+                });
+                let mut outcome = Outcome::Failure;
+                for (score, consideration) in scored {
+                    let option_depth = branch.len();
+                    branch.push(format!("{:.2}", score));
+                    outcome = consideration.node.evaluate(blackboard, branch);
+                    if outcome != Outcome::Failure {
+                        break;
+                    }
+                    branch.truncate(option_depth);
+                }
+                outcome
+            }
+            Node::If(condition) => {
+                if condition.holds(blackboard) {
+                    Outcome::Success(None)
+                } else {
+                    Outcome::Failure
+                }
+            }
+            Node::Do(action) => Outcome::Success(Some(*action)),
+        };
+        if outcome == Outcome::Failure {
+            branch.truncate(depth);
+        }
+        outcome
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Outcome {
+    Failure,
+    /// with the last action decided on, if any
+    Success(Option<Action>),
+}
+
+/// The behaviour trees archetypes refer to by name, loaded from `assets/behaviours.ron`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct BehaviourTrees {
+    pub trees: HashMap<String, Node>,
+    #[serde(skip, default = "Node::fallback")]
+    fallback: Node,
+}
+
+impl Default for Node {
+    fn default() -> Self {
+        Node::fallback()
+    }
+}
+
+impl BehaviourTrees {
+    pub fn load(path: &Path) -> Result<BehaviourTrees, Error> {
+        let file = File::open(path)?;
+        Ok(ron::de::from_reader(file)?)
+    }
+
+    /// Makes sure the tree named by every archetype was loaded, only archetypes without a
+    /// `behaviour` get the fallback tree.
+    pub fn check(&self, archetypes: &EnemyArchetypes) -> Result<(), Error> {
+        for archetype in archetypes.archetypes.iter() {
+            if !archetype.behaviour.is_empty() && !self.trees.contains_key(&archetype.behaviour) {
+                return Err(Error::from_string(format!(
+                    "Unknown behaviour {} for enemy {}",
+                    archetype.behaviour, archetype.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The tree with that name, or the fallback tree for an archetype without one.
+    pub fn get(&self, name: &str) -> &Node {
+        self.trees.get(name).unwrap_or(&self.fallback)
+    }
+}
+
+/// Prints the blackboard of every enemy whose decision changed, turned on with `--debug-ai`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BehaviourDebug {
+    pub enabled: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EnemyArchetype;
+
+    fn decide(node: &Node, blackboard: &Blackboard) -> (Option<Action>, Vec<String>) {
+        let mut branch = vec![String::from("left over")];
+        let action = node.decide(blackboard, &mut branch);
+        (action, branch)
+    }
+
+    fn option(base: f32, bonuses: Vec<(Condition, f32)>, node: Node) -> Consideration {
+        Consideration {
+            base,
+            bonuses,
+            node,
+        }
+    }
+
+    #[test]
+    fn selector_takes_the_first_child_that_succeeds() {
+        let tree = Node::Selector(vec![
+            Node::Sequence(vec![
+                Node::If(Condition::SeesPlayer),
+                Node::Do(Action::Fight),
+            ]),
+            Node::Do(Action::Pursue),
+            Node::Do(Action::Idle),
+        ]);
+        let mut blackboard = Blackboard::default();
+        let (action, branch) = decide(&tree, &blackboard);
+        assert_eq!(action, Some(Action::Pursue));
+        assert_eq!(branch, vec!["Selector", "Do(Pursue)"]);
+
+        blackboard.sees_player = true;
+        let (action, branch) = decide(&tree, &blackboard);
+        assert_eq!(action, Some(Action::Fight));
+        assert_eq!(
+            branch,
+            vec!["Selector", "Sequence", "If(SeesPlayer)", "Do(Fight)"]
+        );
+    }
+
+    #[test]
+    fn sequence_stops_at_the_first_failure() {
+        let tree = Node::Sequence(vec![
+            Node::Do(Action::Advance),
+            Node::If(Condition::Not(Box::new(Condition::OnElevator))),
+            Node::If(Condition::CanShoot),
+            Node::Do(Action::Hold),
+        ]);
+        let mut blackboard = Blackboard::default();
+        assert_eq!(decide(&tree, &blackboard), (None, Vec::new()));
+
+        blackboard.can_shoot = true;
+        assert_eq!(decide(&tree, &blackboard).0, Some(Action::Hold));
+        blackboard.on_elevator = true;
+        assert_eq!(decide(&tree, &blackboard).0, None);
+    }
+
+    #[test]
+    fn utility_tries_the_best_scoring_option_first() {
+        let tree = Node::Utility(vec![
+            option(1., Vec::new(), Node::Do(Action::Hold)),
+            option(
+                0.5,
+                vec![(Condition::BulletIncoming, 1.)],
+                Node::Do(Action::Dodge),
+            ),
+            option(2., Vec::new(), Node::If(Condition::SeesPlayer)),
+        ]);
+        let mut blackboard = Blackboard::default();
+        let (action, branch) = decide(&tree, &blackboard);
+        assert_eq!(action, Some(Action::Hold));
+        assert_eq!(branch, vec!["Utility", "1.00", "Do(Hold)"]);
+
+        blackboard.bullet_incoming = true;
+        let (action, branch) = decide(&tree, &blackboard);
+        assert_eq!(action, Some(Action::Dodge));
+        assert_eq!(branch, vec!["Utility", "1.50", "Do(Dodge)"]);
+    }
+
+    #[test]
+    fn utility_tries_scores_that_are_not_numbers_last() {
+        let tree = Node::Utility(vec![
+            option(std::f32::NAN, Vec::new(), Node::Do(Action::Dodge)),
+            option(1., Vec::new(), Node::If(Condition::CanShoot)),
+            option(
+                0.,
+                vec![(Condition::NearDoor, std::f32::NAN)],
+                Node::Do(Action::Idle),
+            ),
+            option(2., Vec::new(), Node::If(Condition::SeesPlayer)),
+            option(0.5, Vec::new(), Node::Do(Action::Retreat)),
+        ]);
+        let blackboard = Blackboard {
+            near_door: true,
+            ..Blackboard::default()
+        };
+        assert_eq!(decide(&tree, &blackboard).0, Some(Action::Retreat));
+
+        let tree = Node::Utility(vec![
+            option(1., Vec::new(), Node::If(Condition::CanShoot)),
+            option(std::f32::NAN, Vec::new(), Node::Do(Action::Dodge)),
+        ]);
+        let (action, branch) = decide(&tree, &blackboard);
+        assert_eq!(action, Some(Action::Dodge));
+        assert_eq!(branch, vec!["Utility", "NaN", "Do(Dodge)"]);
+    }
+
+    #[test]
+    fn fallback_tree_dodges_before_fighting() {
+        let mut blackboard = Blackboard {
+            sees_player: true,
+            ..Blackboard::default()
+        };
+        assert_eq!(
+            decide(&Node::fallback(), &blackboard).0,
+            Some(Action::Fight)
+        );
+        blackboard.bullet_incoming = true;
+        assert_eq!(
+            decide(&Node::fallback(), &blackboard).0,
+            Some(Action::Dodge)
+        );
+    }
+
+    #[test]
+    fn dumps_the_facts_and_the_branch() {
+        let mut blackboard = Blackboard {
+            sees_player: true,
+            near_door: true,
+            ..Blackboard::default()
+        };
+        blackboard.branch = decide(&Node::fallback(), &blackboard).1;
+        assert_eq!(
+            blackboard.dump(),
+            "[sees_player, near_door] Selector > Sequence > If(SeesPlayer) > Do(Fight)"
+        );
+    }
+
+    #[test]
+    fn checks_every_named_tree_was_loaded() {
+        let mut trees = BehaviourTrees::default();
+        trees
+            .trees
+            .insert(String::from("soldier"), Node::Do(Action::Hold));
+        let archetypes = |behaviour: &str| EnemyArchetypes {
+            archetypes: vec![
+                EnemyArchetype::default(),
+                EnemyArchetype {
+                    behaviour: behaviour.to_string(),
+                    ..EnemyArchetype::default()
+                },
+            ],
+            spawn_tables: Vec::new(),
+        };
+        assert!(trees.check(&archetypes("soldier")).is_ok());
+        assert!(trees.check(&archetypes("ghost")).is_err());
+        assert_eq!(trees.get("soldier"), &Node::Do(Action::Hold));
+        assert_eq!(trees.get(""), &Node::fallback());
+    }
+}
@@ -0,0 +1,41 @@
+/// Limits how often the enemies, all together, get to shoot.
+///
+/// Shots build up over time to `max_shots`, and every enemy bullet uses one. The rates come
+/// from the `Difficulty`.
+#[derive(Clone, Debug)]
+pub struct FireBudget {
+    /// shots added each second
+    pub refill_rate: f32,
+    pub max_shots: f32,
+    pub shots: f32,
+}
+
+impl Default for FireBudget {
+    fn default() -> Self {
+        FireBudget::new(0.6, 2.)
+    }
+}
+
+impl FireBudget {
+    pub fn new(refill_rate: f32, max_shots: f32) -> Self {
+        FireBudget {
+            refill_rate,
+            max_shots,
+            shots: 0.,
+        }
+    }
+
+    pub fn refill(&mut self, seconds: f32) {
+        self.shots = (self.shots + self.refill_rate * seconds).min(self.max_shots);
+    }
+
+    /// Uses up a shot, returns false when there are none left.
+    pub fn take(&mut self) -> bool {
+        if self.shots >= 1. {
+            self.shots -= 1.;
+            true
+        } else {
+            false
+        }
+    }
+}
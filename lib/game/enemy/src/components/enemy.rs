@@ -1,20 +1,42 @@
 use amethyst::ecs::{Component, DenseVecStorage, Entity};
+use serde::{Deserialize, Serialize};
+
+use crate::archetype::EnemyArchetype;
+use crate::behaviour::Blackboard;
+
+/// How an enemy fights once he can see the player.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Tactic {
+    /// walk up to the player
+    Advance,
+    /// stay put and shoot
+    Hold,
+    /// back off to the closest door
+    Retreat,
+}
 
 // TODO: does all of this stuff really need to be public?
 #[derive(Component)]
 #[storage(DenseVecStorage)]
 pub struct Enemy {
+    pub archetype: EnemyArchetype,
     pub spawn_time: f64,
     pub pursuing_entity: Option<Entity>,
     pub pursuit_time: f64,
+    /// whether he was on the player's floor last time he looked
+    pub was_on_player_floor: bool,
+    pub blackboard: Blackboard,
 }
 
 impl Enemy {
-    pub fn new(spawn_time: f64) -> Enemy {
+    pub fn new(archetype: EnemyArchetype, spawn_time: f64) -> Enemy {
         Enemy {
+            archetype,
             spawn_time,
             pursuing_entity: None,
             pursuit_time: 0.,
+            was_on_player_floor: false,
+            blackboard: Blackboard::default(),
         }
     }
 }
@@ -1,3 +1,3 @@
 mod enemy;
 
-pub use enemy::Enemy;
+pub use enemy::{Enemy, Tactic};
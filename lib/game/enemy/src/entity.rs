@@ -6,26 +6,43 @@ use amethyst::{
     ecs::{Entities, Entity, LazyUpdate, ReadExpect},
 };
 
+use crate::archetype::EnemyArchetype;
 use crate::components::Enemy;
 
 use animation::components::{Animation, AnimationId, AnimationPrefabData};
+use elevator::components::ElevatorCall;
+use escalator::components::EscalatorControl;
 use floors::Floor;
 use hierarchy::components::Child;
 use person::components::{Gun, Person};
 use physics::components::{Collidee, Collider, Direction, Directions, Motion, Proximity};
+use score::Bounty;
 
 const ENEMY_Z: f32 = 0.5;
 
-/// Initialises one player in the middle-ish space
+/// Who comes out of a door, where and when.
+pub struct EnemySpawn<'a> {
+    pub archetype: &'a EnemyArchetype,
+    /// the top left corner of the enemy
+    pub position: Vector2<f32>,
+    pub spawn_time: f64,
+    pub floors_overlapped: Vec<usize>,
+}
+
+/// Initialises one enemy of the given archetype
 pub fn spawn_enemy(
     entities: &Entities,
     lazy_update: &ReadExpect<LazyUpdate>,
     enemy_prefab_handle: Handle<Prefab<AnimationPrefabData>>,
     guns_prefab_handle: Handle<Prefab<AnimationPrefabData>>,
-    position: Vector2<f32>,
-    spawn_time: f64,
-    floors_overlapped: Vec<usize>,
+    spawn: EnemySpawn,
 ) {
+    let EnemySpawn {
+        archetype,
+        position,
+        spawn_time,
+        floors_overlapped,
+    } = spawn;
     let mut transform = Transform::default();
     transform.set_translation_z(ENEMY_Z);
 
@@ -38,8 +55,12 @@ pub fn spawn_enemy(
 
     let enemy_entity: Entity = entities.create();
     lazy_update.insert(enemy_entity, Named::new("Enemy"));
-    lazy_update.insert(enemy_entity, Enemy::new(spawn_time));
-    lazy_update.insert(enemy_entity, Person::new());
+    lazy_update.insert(enemy_entity, Enemy::new(archetype.clone(), spawn_time));
+    let mut person = Person::new();
+    person.max_ground_speed = archetype.max_ground_speed;
+    person.max_jump_velocity = archetype.max_jump_velocity;
+    lazy_update.insert(enemy_entity, person);
+    lazy_update.insert(enemy_entity, Bounty::new(archetype.score));
     lazy_update.insert(enemy_entity, collider);
     lazy_update.insert(enemy_entity, Collidee::default());
     lazy_update.insert(enemy_entity, transform);
@@ -69,6 +90,8 @@ pub fn spawn_enemy(
         ),
     );
     lazy_update.insert(enemy_entity, Proximity::default());
+    lazy_update.insert(enemy_entity, EscalatorControl::default());
+    lazy_update.insert(enemy_entity, ElevatorCall::default());
     lazy_update.insert(
         enemy_entity,
         Floor::new(
@@ -82,7 +105,9 @@ pub fn spawn_enemy(
     let gun_entity: Entity = entities.create();
     lazy_update.insert(gun_entity, Named::new("Gun"));
     lazy_update.insert(gun_entity, Child::new(enemy_entity, 8., 2., 0.));
-    lazy_update.insert(gun_entity, Gun::new(false));
+    let mut gun = Gun::new(false);
+    gun.weapon.bullet_velocity = archetype.bullet_velocity;
+    lazy_update.insert(gun_entity, gun);
     lazy_update.insert(gun_entity, gun_transform);
     lazy_update.insert(
         gun_entity,
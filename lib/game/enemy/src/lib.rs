@@ -1,5 +1,15 @@
 pub mod components;
 pub mod systems;
 
+mod archetype;
+mod behaviour;
+mod combat;
 mod entity;
-pub use entity::spawn_enemy;
+mod navigation;
+pub use archetype::{EnemyArchetype, EnemyArchetypes, SpawnChance, SpawnTable};
+pub use behaviour::{
+    Action, BehaviourDebug, BehaviourTrees, Blackboard, Condition, Consideration, Node,
+};
+pub use combat::FireBudget;
+pub use entity::{spawn_enemy, EnemySpawn};
+pub use navigation::{Link, NavigationGraph, Step};
@@ -0,0 +1,239 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, VecDeque},
+};
+
+use amethyst::core::math::Vector2;
+
+/// A way of getting from one floor to another.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Link {
+    /// An elevator shaft, `x` is the middle of the shaft.
+    Elevator {
+        x: f32,
+        min_floor: usize,
+        max_floor: usize,
+    },
+    /// An escalator between `bottom_floor` and the floor above it, with the points where the
+    /// steps meet the floors.
+    Escalator {
+        bottom: Vector2<f32>,
+        top: Vector2<f32>,
+        bottom_floor: usize,
+    },
+    /// Stairs down from `top_floor` to the floor below, an opening in what the floor stands on.
+    /// They're dropped through, `x` is the middle of the opening, so they only go down.
+    Stairs { x: f32, top_floor: usize },
+}
+
+impl Link {
+    pub fn connects(&self, floor: usize) -> bool {
+        match self {
+            Link::Elevator {
+                min_floor,
+                max_floor,
+                ..
+            } => *min_floor <= floor && floor <= *max_floor,
+            Link::Escalator { bottom_floor, .. } => {
+                floor == *bottom_floor || floor == *bottom_floor + 1
+            }
+            Link::Stairs { top_floor, .. } => floor == *top_floor || floor + 1 == *top_floor,
+        }
+    }
+
+    /// The floors that can be reached from `floor` with this link.
+    pub fn exits(&self, floor: usize) -> Vec<usize> {
+        if !self.connects(floor) {
+            return Vec::new();
+        }
+        match self {
+            Link::Elevator {
+                min_floor,
+                max_floor,
+                ..
+            } => (*min_floor..=*max_floor).filter(|f| *f != floor).collect(),
+            Link::Escalator { bottom_floor, .. } => {
+                if floor == *bottom_floor {
+                    vec![*bottom_floor + 1]
+                } else {
+                    vec![*bottom_floor]
+                }
+            }
+            Link::Stairs { top_floor, .. } => {
+                if floor == *top_floor {
+                    vec![*top_floor - 1]
+                } else {
+                    Vec::new()
+                }
+            }
+        }
+    }
+}
+
+/// The first part of a route, take `link` and get off at `exit_floor`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Step {
+    pub link: Link,
+    pub exit_floor: usize,
+}
+
+/// How the floors of a building are connected, built from the map when a level is loaded.
+#[derive(Clone, Debug, Default)]
+pub struct NavigationGraph {
+    pub links: Vec<Link>,
+    /// the middle of every door, by floor
+    pub doors: BTreeMap<usize, Vec<f32>>,
+}
+
+impl NavigationGraph {
+    pub fn add_link(&mut self, link: Link) {
+        self.links.push(link);
+    }
+
+    pub fn add_door(&mut self, floor: usize, x: f32) {
+        self.doors.entry(floor).or_default().push(x);
+    }
+
+    /// Plans the shortest route (in links taken) between two floors and returns its first step.
+    pub fn plan(&self, from: usize, to: usize) -> Option<Step> {
+        if from == to {
+            return None;
+        }
+        // the first step taken to get to each floor
+        let mut first_steps: BTreeMap<usize, Option<Step>> = BTreeMap::new();
+        first_steps.insert(from, None);
+        let mut pending: VecDeque<usize> = VecDeque::new();
+        pending.push_back(from);
+        while let Some(floor) = pending.pop_front() {
+            let first_step = first_steps.get(&floor).cloned().unwrap_or(None);
+            for link in self.links.iter() {
+                for exit_floor in link.exits(floor) {
+                    if first_steps.contains_key(&exit_floor) {
+                        continue;
+                    }
+                    let step = first_step.clone().unwrap_or_else(|| Step {
+                        link: link.clone(),
+                        exit_floor,
+                    });
+                    if exit_floor == to {
+                        return Some(step);
+                    }
+                    first_steps.insert(exit_floor, Some(step));
+                    pending.push_back(exit_floor);
+                }
+            }
+        }
+        None
+    }
+
+    /// How many links have to be taken to get between two floors.
+    pub fn distance(&self, from: usize, to: usize) -> Option<usize> {
+        let mut distances: BTreeMap<usize, usize> = BTreeMap::new();
+        distances.insert(from, 0);
+        let mut pending: VecDeque<usize> = VecDeque::new();
+        pending.push_back(from);
+        while let Some(floor) = pending.pop_front() {
+            let distance = distances[&floor];
+            if floor == to {
+                return Some(distance);
+            }
+            for link in self.links.iter() {
+                for exit_floor in link.exits(floor) {
+                    if !distances.contains_key(&exit_floor) {
+                        distances.insert(exit_floor, distance + 1);
+                        pending.push_back(exit_floor);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// The door closest to `x` on a floor.
+    pub fn nearest_door(&self, floor: usize, x: f32) -> Option<f32> {
+        self.doors.get(&floor).and_then(|doors| {
+            doors.iter().cloned().min_by(|a, b| {
+                (a - x)
+                    .abs()
+                    .partial_cmp(&(b - x).abs())
+                    .unwrap_or(Ordering::Equal)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Floors 0 to 5: an elevator from 3 to 5, an escalator from 2 to 3, stairs down from 2 and
+    /// an elevator from 0 to 1.
+    fn building() -> NavigationGraph {
+        let mut navigation = NavigationGraph::default();
+        navigation.add_link(Link::Elevator {
+            x: 100.,
+            min_floor: 3,
+            max_floor: 5,
+        });
+        navigation.add_link(Link::Escalator {
+            bottom: Vector2::new(20., 0.),
+            top: Vector2::new(60., 48.),
+            bottom_floor: 2,
+        });
+        navigation.add_link(Link::Stairs {
+            x: 12.,
+            top_floor: 2,
+        });
+        navigation.add_link(Link::Elevator {
+            x: 200.,
+            min_floor: 0,
+            max_floor: 1,
+        });
+        navigation
+    }
+
+    #[test]
+    fn rides_an_elevator_straight_to_the_floor() {
+        let step = building().plan(5, 3).unwrap();
+        assert_eq!(step.exit_floor, 3);
+        assert_eq!(step.link, building().links[0]);
+        assert_eq!(building().distance(5, 3), Some(1));
+    }
+
+    #[test]
+    fn plans_the_first_step_of_a_longer_route() {
+        let navigation = building();
+        let step = navigation.plan(5, 0).unwrap();
+        assert_eq!(step.link, navigation.links[0]);
+        assert_eq!(step.exit_floor, 3);
+        assert_eq!(navigation.distance(5, 0), Some(4));
+
+        let step = navigation.plan(3, 1).unwrap();
+        assert_eq!(step.link, navigation.links[1]);
+        assert_eq!(step.exit_floor, 2);
+    }
+
+    #[test]
+    fn stairs_only_go_down() {
+        let navigation = building();
+        assert_eq!(navigation.plan(2, 1).unwrap().link, navigation.links[2]);
+        assert_eq!(navigation.plan(1, 2), None);
+        assert_eq!(navigation.distance(0, 5), None);
+    }
+
+    #[test]
+    fn has_nothing_to_plan_on_the_same_floor() {
+        assert_eq!(building().plan(4, 4), None);
+        assert_eq!(building().distance(4, 4), Some(0));
+    }
+
+    #[test]
+    fn finds_the_nearest_door() {
+        let mut navigation = building();
+        navigation.add_door(1, 40.);
+        navigation.add_door(1, 180.);
+        assert_eq!(navigation.nearest_door(1, 150.), Some(180.));
+        assert_eq!(navigation.nearest_door(1, std::f32::NAN), Some(40.));
+        assert_eq!(navigation.nearest_door(2, 150.), None);
+    }
+}
@@ -1,21 +1,33 @@
 use amethyst::{
     core::{math::Vector2, timing::Time},
-    ecs::{Entities, Entity, Join, Read, ReadStorage, System, WriteStorage},
+    ecs::{Entities, Entity, Join, Read, ReadStorage, System, Write, WriteStorage},
 };
 
 use crate::components::Enemy;
+use crate::navigation::{Link, NavigationGraph};
 
 use array_tool::vec::Intersect;
-use floors::Floor;
+use elevator::components::{Elevator, ElevatorCall};
+use escalator::components::EscalatorControl;
+use floors::{Floor, FloorsDrawn};
+use level::Difficulty;
 use person::components::{Person, PersonState};
-use physics::components::{Direction, Directions};
+use physics::components::{Collider, Direction, Directions};
 use player::components::Player;
 use rand::Rng;
+use random::{GameRng, RngStream};
 
-const PURSUIT_FACTOR_SAME_FLOOR: f32 = 0.05;
-// const CONTINUE_PURSUIT_FACTOR: f32 = 0.1;
-// const PURSUIT_DIFFERENT_FLOOR: f32 = 0.001;
+// chance of going after the player when he leaves the floor
+const CONTINUE_PURSUIT_FACTOR: f32 = 0.1;
+// chance (each frame) of going after the player on another floor
+const PURSUIT_DIFFERENT_FLOOR: f32 = 0.001;
 const MIN_PURSUIT_TIME: f64 = 1.0;
+const ESCALATOR_FOOT_DISTANCE: f32 = 4.0;
+// where to wait for an elevator, from the middle of the shaft
+const SHAFT_WAIT_DISTANCE: f32 = 20.0;
+const CAB_HALF_WIDTH: f32 = 12.0;
+const CAB_HEIGHT: f32 = 40.0;
+const ARRIVE_DISTANCE: f32 = 2.0;
 
 pub struct EnemyAISystem;
 
@@ -27,13 +39,37 @@ impl<'s> System<'s> for EnemyAISystem {
         ReadStorage<'s, Player>,
         ReadStorage<'s, Floor>,
         WriteStorage<'s, Direction>,
+        ReadStorage<'s, Collider>,
+        ReadStorage<'s, Elevator>,
+        WriteStorage<'s, EscalatorControl>,
+        WriteStorage<'s, ElevatorCall>,
+        Read<'s, NavigationGraph>,
+        Read<'s, FloorsDrawn>,
+        Read<'s, Difficulty>,
         Read<'s, Time>,
+        Write<'s, GameRng>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (entities, mut enemies, mut persons, players, floors, mut directions, time) = data;
+        let (
+            entities,
+            mut enemies,
+            mut persons,
+            players,
+            floors,
+            mut directions,
+            colliders,
+            elevators,
+            mut escalator_controls,
+            mut elevator_calls,
+            navigation,
+            floors_drawn,
+            difficulty,
+            time,
+            mut game_rng,
+        ) = data;
         let current_time = time.absolute_time_seconds();
-        let mut rng = rand::thread_rng();
+        let rng = game_rng.stream(RngStream::EnemyAi);
 
         // start with this...
         // a) if the player is on the same floor, turn towards them
@@ -42,27 +78,57 @@ impl<'s> System<'s> for EnemyAISystem {
         // 1. get the player floor and location
         let mut player_position: Vector2<f32> = Vector2::new(0., 0.);
         let mut player_floor: Vec<usize> = Vec::new();
+        let mut player_standing_floor: Option<usize> = None;
         let mut player_entity: Option<Entity> = None;
-        for (entity, person, _player, floor) in (&entities, &persons, &players, &floors).join() {
+        for (entity, person, _player, floor, collider) in
+            (&entities, &persons, &players, &floors, &colliders).join()
+        {
             player_position.x = person.position.x;
             player_position.y = person.position.y;
             player_floor = floor.floors_overlapped.clone();
+            player_standing_floor = standing_floor(&floors_drawn, collider);
             player_entity = Some(entity);
         }
 
         // 2. find all plauyers
         if let Some(pursuit_entity) = player_entity {
-            for (_entity, person, enemy, floor, direction) in (
+            for (
+                _entity,
+                person,
+                enemy,
+                floor,
+                direction,
+                collider,
+                escalator_control,
+                elevator_call,
+            ) in (
                 &entities,
                 &mut persons,
                 &mut enemies,
                 &floors,
                 &mut directions,
+                &colliders,
+                &mut escalator_controls,
+                &mut elevator_calls,
             )
                 .join()
             {
+                // the escalator takes over until he's off
+                if person.state == PersonState::RidingEscalator {
+                    continue;
+                }
+                escalator_control.up = false;
+                escalator_control.down = false;
+                elevator_call.up = false;
+                elevator_call.down = false;
+                elevator_call.floor = None;
+                if person.state == PersonState::Dying || person.state == PersonState::KnockedDown {
+                    continue;
+                }
+
                 let overlap = player_floor.intersect(floor.floors_overlapped.clone());
                 if overlap.len() > 0 {
+                    enemy.was_on_player_floor = true;
                     if person.position.x >= player_position.x {
                         direction.x = Directions::Left;
                     } else {
@@ -80,7 +146,7 @@ impl<'s> System<'s> for EnemyAISystem {
                         }
                     } else {
                         let random_number: i32 =
-                            rng.gen_range(0, (1. / PURSUIT_FACTOR_SAME_FLOOR) as i32);
+                            rng.gen_range(0, (1. / difficulty.pursuit_factor_same_floor()) as i32);
                         if random_number == 0 {
                             enemy.pursuit_time = current_time;
                             enemy.pursuing_entity = Some(pursuit_entity);
@@ -89,22 +155,156 @@ impl<'s> System<'s> for EnemyAISystem {
                         }
                     }
 
-                    if person.state != PersonState::Dying {
-                        if enemy.pursuing_entity.is_some() {
-                            person.state = PersonState::Walking;
-                        } else {
-                            person.state = PersonState::Idling;
+                    if enemy.pursuing_entity.is_some() {
+                        person.state = PersonState::Walking;
+                    } else {
+                        person.state = PersonState::Idling;
+                    }
+                    continue;
+                }
+
+                // on a different floor, decide whether to go after the player
+                if enemy.pursuing_entity.is_some() {
+                    if enemy.was_on_player_floor && rng.gen::<f32>() >= CONTINUE_PURSUIT_FACTOR {
+                        enemy.pursuing_entity = None;
+                    }
+                } else if rng.gen::<f32>() < PURSUIT_DIFFERENT_FLOOR {
+                    enemy.pursuit_time = current_time;
+                    enemy.pursuing_entity = Some(pursuit_entity);
+                }
+                enemy.was_on_player_floor = false;
+
+                let bbox = &collider.bounding_box;
+                let feet = Vector2::new(bbox.position.x, bbox.position.y - bbox.half_size.y);
+                let target_floor = match player_standing_floor {
+                    Some(target_floor) => target_floor,
+                    None => continue,
+                };
+
+                // inside a cab, ride it to the floor that gets him closest to the player
+                let cab = (&elevators).join().find(|elevator| {
+                    collider.on_elevator
+                        && (elevator.position.x - feet.x).abs() < CAB_HALF_WIDTH
+                        && feet.y < elevator.position.y
+                        && feet.y > elevator.position.y - CAB_HEIGHT
+                });
+                if let Some(elevator) = cab {
+                    let exit_floor = (elevator.min_floor..=elevator.max_floor())
+                        .min_by_key(|f| {
+                            (
+                                navigation
+                                    .distance(*f, target_floor)
+                                    .unwrap_or(usize::max_value()),
+                                (*f as i32 - target_floor as i32).abs(),
+                            )
+                        })
+                        .unwrap_or(target_floor);
+                    if elevator.velocity == 0. && elevator.floor() == exit_floor {
+                        walk_towards(person, direction, feet.x, player_position.x, 0.);
+                    } else {
+                        elevator_call.floor = Some(exit_floor);
+                        person.state = PersonState::Idling;
+                    }
+                    continue;
+                }
+
+                if enemy.pursuing_entity.is_none() {
+                    person.state = PersonState::Idling;
+                    continue;
+                }
+                let current_floor = match standing_floor(&floors_drawn, collider) {
+                    Some(current_floor) => current_floor,
+                    None => continue,
+                };
+                match navigation.plan(current_floor, target_floor) {
+                    Some(step) => match step.link {
+                        Link::Escalator {
+                            bottom,
+                            top,
+                            bottom_floor,
+                        } => {
+                            let going_up = step.exit_floor > bottom_floor;
+                            let start = if going_up { bottom } else { top };
+                            if walk_towards(
+                                person,
+                                direction,
+                                feet.x,
+                                start.x,
+                                ESCALATOR_FOOT_DISTANCE,
+                            ) {
+                                escalator_control.up = going_up;
+                                escalator_control.down = !going_up;
+                            }
+                        }
+                        Link::Stairs { x, .. } => {
+                            // stops over the opening and drops through it
+                            walk_towards(person, direction, feet.x, x, ARRIVE_DISTANCE);
+                        }
+                        Link::Elevator { x, .. } => {
+                            let cab_is_here = (&elevators).join().any(|elevator| {
+                                (elevator.position.x - x).abs() < 1.
+                                    && elevator.velocity == 0.
+                                    && elevator.floor_offset == 0.
+                                    && elevator.current_floor == current_floor
+                            });
+                            if cab_is_here {
+                                // get in
+                                walk_towards(person, direction, feet.x, x, ARRIVE_DISTANCE);
+                            } else {
+                                // wait next to the shaft, on the side he came from
+                                let wait_x = if feet.x < x {
+                                    x - SHAFT_WAIT_DISTANCE
+                                } else {
+                                    x + SHAFT_WAIT_DISTANCE
+                                };
+                                if walk_towards(person, direction, feet.x, wait_x, ARRIVE_DISTANCE)
+                                {
+                                    elevator_call.up = step.exit_floor > current_floor;
+                                    elevator_call.down = !elevator_call.up;
+                                }
+                            }
+                        }
+                    },
+                    None => {
+                        // no way to get there, hang around the closest door instead
+                        match navigation.nearest_door(current_floor, feet.x) {
+                            Some(door_x) => {
+                                walk_towards(person, direction, feet.x, door_x, ARRIVE_DISTANCE);
+                            }
+                            None => person.state = PersonState::Idling,
                         }
                     }
                 }
             }
         }
+    }
+}
+
+/// The floor someone is standing on (or falling through).
+pub(crate) fn standing_floor(floors_drawn: &FloorsDrawn, collider: &Collider) -> Option<usize> {
+    let bbox = &collider.bounding_box;
+    let feet = Vector2::new(bbox.position.x, bbox.position.y - bbox.half_size.y + 1.);
+    floors_drawn.find_floors(feet, 0., 0.).first().cloned()
+}
 
-        // TODO:
-        // if on the same floor, pursue automatically
-        //      % chance for shooting at user based on time since last shot
-        // on a different floor
-        //      decide if enemy is pursuing
-        //
+/// Walks towards `to_x`, returns whether he is already there.
+pub(crate) fn walk_towards(
+    person: &mut Person,
+    direction: &mut Direction,
+    from_x: f32,
+    to_x: f32,
+    distance: f32,
+) -> bool {
+    if (to_x - from_x).abs() <= distance {
+        person.state = PersonState::Idling;
+        true
+    } else {
+        direction.x = if to_x < from_x {
+            Directions::Left
+        } else {
+            Directions::Right
+        };
+        person.state = PersonState::Walking;
+        false
     }
 }
@@ -0,0 +1,345 @@
+use amethyst::{
+    core::{math::Vector2, timing::Time, Named},
+    ecs::{Entities, Entity, Join, Read, ReadStorage, System, Write, WriteStorage},
+};
+
+use super::ai::{standing_floor, walk_towards};
+use crate::behaviour::{Action, BehaviourDebug, BehaviourTrees};
+use crate::combat::FireBudget;
+use crate::components::{Enemy, Tactic};
+use crate::navigation::NavigationGraph;
+
+use floors::FloorsDrawn;
+use hierarchy::components::Child;
+use level::Difficulty;
+use person::components::{Bullet, Gun, GunState, Person, PersonState};
+use physics::components::{Collider, Direction, Directions, Proximity};
+use player::components::Player;
+use rand::{rngs::StdRng, Rng};
+use random::{GameRng, RngStream};
+
+// how far apart (up and down) two people can be and still see each other
+const SIGHT_HEIGHT: f32 = 16.0;
+// how far above the player's middle a missed shot goes
+const MISS_OFFSET: f32 = 16.0;
+// how long the gun stays out after a shot, same as the player's
+const SHOT_SECONDS: f64 = 0.05;
+// each enemy has only one bullet in the air at a time
+const MAX_SHOTS: i32 = 1;
+// chance (each frame) of pulling the trigger when he can
+const SHOOT_FACTOR: f32 = 0.05;
+const TACTIC_SECONDS: f64 = 2.0;
+// how close he gets when advancing
+const ADVANCE_DISTANCE: f32 = 48.0;
+const ARRIVE_DISTANCE: f32 = 2.0;
+// how close to a door counts as being at it
+const NEAR_DOOR_DISTANCE: f32 = 16.0;
+// chance of noticing a bullet coming at him
+const DODGE_FACTOR: f32 = 0.5;
+const DODGE_SECONDS: f64 = 0.4;
+
+/// Shooting at the player, getting out of the way of his bullets and picking a tactic.
+///
+/// Runs after the `EnemyAISystem`, every enemy's behaviour tree decides whether to leave him
+/// to the pursuit or to take over.
+pub struct EnemyCombatSystem;
+
+impl<'s> System<'s> for EnemyCombatSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Enemy>,
+        WriteStorage<'s, Person>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Collider>,
+        WriteStorage<'s, Direction>,
+        WriteStorage<'s, Gun>,
+        ReadStorage<'s, Child>,
+        ReadStorage<'s, Bullet>,
+        ReadStorage<'s, Proximity>,
+        ReadStorage<'s, Named>,
+        Read<'s, NavigationGraph>,
+        Read<'s, BehaviourTrees>,
+        Read<'s, BehaviourDebug>,
+        Read<'s, FloorsDrawn>,
+        Read<'s, Difficulty>,
+        Read<'s, Time>,
+        Write<'s, FireBudget>,
+        Write<'s, GameRng>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            mut enemies,
+            mut persons,
+            players,
+            colliders,
+            mut directions,
+            mut guns,
+            children,
+            bullets,
+            proximities,
+            names,
+            navigation,
+            behaviour_trees,
+            behaviour_debug,
+            floors_drawn,
+            difficulty,
+            time,
+            mut fire_budget,
+            mut game_rng,
+        ) = data;
+        let current_time = time.absolute_time_seconds();
+        let rng = game_rng.stream(RngStream::EnemyCombat);
+        fire_budget.refill_rate = difficulty.fire_rate();
+        fire_budget.max_shots = difficulty.settings.max_shots;
+        fire_budget.refill(time.delta_seconds());
+
+        // 1. the player's bullets that are about to hit an enemy
+        let mut incoming: Vec<(Entity, Entity)> = Vec::new();
+        for (bullet_entity, bullet, proximity) in (&entities, &bullets, &proximities).join() {
+            let shot_by_player = bullet
+                .parent
+                .and_then(|parent| guns.get(parent))
+                .map_or(false, |gun| gun.is_player);
+            if !shot_by_player {
+                continue;
+            }
+            for details in proximity.details.iter() {
+                if details.approaching && details.other_name == "Enemy" {
+                    incoming.push((details.other_entity, bullet_entity));
+                }
+            }
+        }
+
+        // 2. where the player is, as long as he can be seen at all
+        let mut target: Option<(Vector2<f32>, usize)> = None;
+        for (person, _player, collider) in (&persons, &players, &colliders).join() {
+            if person.state == PersonState::Dying || person.state.is_in_room() {
+                continue;
+            }
+            if let Some(floor) = standing_floor(&floors_drawn, collider) {
+                target = Some((collider.bounding_box.position, floor));
+            }
+        }
+        let walls: Vec<&Collider> = (&colliders, &names)
+            .join()
+            .filter(|(_collider, named)| named.name == "wall")
+            .map(|(collider, _named)| collider)
+            .collect();
+
+        // 3. fill in every blackboard, let the trees decide and act on it, the guns follow
+        // afterwards with the aim of any shot
+        let mut gun_orders: Vec<(Entity, Directions, Option<f32>)> = Vec::new();
+        for (entity, enemy, person, collider, direction) in (
+            &entities,
+            &mut enemies,
+            &mut persons,
+            &colliders,
+            &mut directions,
+        )
+            .join()
+        {
+            let blackboard = &mut enemy.blackboard;
+            if person.state == PersonState::Dying
+                || person.state == PersonState::KnockedDown
+                || person.state == PersonState::RidingEscalator
+            {
+                blackboard.reset_combat();
+                gun_orders.push((entity, direction.x, None));
+                continue;
+            }
+            let position = collider.bounding_box.position;
+            let current_floor = standing_floor(&floors_drawn, collider);
+
+            // he gets one chance to notice each bullet coming at him, and forgets about the
+            // one he already dodged
+            let bullet = incoming
+                .iter()
+                .find(|(enemy_entity, bullet)| {
+                    *enemy_entity == entity && Some(*bullet) != blackboard.dodged_bullet
+                })
+                .map(|(_enemy, bullet)| *bullet);
+            if bullet != blackboard.seen_bullet {
+                blackboard.seen_bullet = bullet;
+                blackboard.bullet_incoming = bullet.is_some() && rng.gen::<f32>() < DODGE_FACTOR;
+            }
+
+            let in_sight = target.filter(|(player_position, player_floor)| {
+                current_floor == Some(*player_floor)
+                    && (player_position.y - position.y).abs() <= SIGHT_HEIGHT
+                    && !walls
+                        .iter()
+                        .any(|wall| blocks_view(wall, position, *player_position))
+            });
+            blackboard.sees_player = in_sight.is_some();
+            if blackboard.sees_player {
+                blackboard.sighted_time.get_or_insert(current_time);
+            } else {
+                blackboard.sighted_time = None;
+                blackboard.tactic = None;
+            }
+            let gun_ready = (&guns, &children).join().any(|(gun, child)| {
+                child.parent == entity
+                    && gun.state == GunState::Holstered
+                    && gun.shots_fired < MAX_SHOTS
+            });
+            blackboard.can_shoot = gun_ready
+                && fire_budget.shots >= 1.
+                && blackboard.sighted_time.map_or(false, |sighted_time| {
+                    current_time - sighted_time > enemy.archetype.reaction_time
+                });
+            blackboard.on_elevator = collider.on_elevator;
+            let door = current_floor.and_then(|floor| navigation.nearest_door(floor, position.x));
+            blackboard.near_door = door.map_or(false, |door_x| {
+                (door_x - position.x).abs() <= NEAR_DOOR_DISTANCE
+            });
+            blackboard.pursuing = enemy.pursuing_entity.is_some();
+
+            let mut branch: Vec<String> = Vec::new();
+            let action = behaviour_trees
+                .get(&enemy.archetype.behaviour)
+                .decide(blackboard, &mut branch);
+            let changed = action != blackboard.action || branch != blackboard.branch;
+            blackboard.action = action;
+            blackboard.branch = branch;
+            if behaviour_debug.enabled && changed {
+                println!("### Enemy {}: {} ###", entity.id(), blackboard.dump());
+            }
+
+            // a dodge isn't called off halfway, a jump lasts until he lands again
+            if let Some(dodge) = blackboard.dodge {
+                if current_time - blackboard.dodge_time < DODGE_SECONDS
+                    || (dodge == PersonState::Jumping && !collider.on_ground)
+                {
+                    person.state = dodge;
+                    gun_orders.push((entity, direction.x, None));
+                    continue;
+                }
+                blackboard.dodge = None;
+            }
+
+            let tactic = match action {
+                Some(Action::Dodge) => {
+                    if collider.on_ground {
+                        let dodge = if rng.gen::<bool>() {
+                            PersonState::Ducking
+                        } else {
+                            PersonState::Jumping
+                        };
+                        blackboard.dodge = Some(dodge);
+                        blackboard.dodge_time = current_time;
+                        blackboard.dodged_bullet = blackboard.seen_bullet;
+                        person.state = dodge;
+                    }
+                    gun_orders.push((entity, direction.x, None));
+                    continue;
+                }
+                Some(Action::Fight) => {
+                    if blackboard.tactic.is_none()
+                        || current_time - blackboard.tactic_time > TACTIC_SECONDS
+                    {
+                        blackboard.tactic = Some(pick_tactic(&enemy.archetype.tactics, rng));
+                        blackboard.tactic_time = current_time;
+                    }
+                    blackboard.tactic
+                }
+                Some(Action::Advance) => Some(Tactic::Advance),
+                Some(Action::Hold) => Some(Tactic::Hold),
+                Some(Action::Retreat) => Some(Tactic::Retreat),
+                Some(Action::Idle) => {
+                    person.state = PersonState::Idling;
+                    None
+                }
+                // the pursuit was already taken care of by the EnemyAISystem
+                Some(Action::Pursue) | None => None,
+            };
+
+            let player_x = in_sight.map(|(player_position, _player_floor)| player_position.x);
+            let facing_player = match (tactic, player_x) {
+                (Some(Tactic::Advance), Some(player_x)) => {
+                    walk_towards(person, direction, position.x, player_x, ADVANCE_DISTANCE);
+                    true
+                }
+                (Some(Tactic::Retreat), _) => match door {
+                    Some(door_x) => {
+                        walk_towards(person, direction, position.x, door_x, ARRIVE_DISTANCE)
+                    }
+                    None => {
+                        person.state = PersonState::Idling;
+                        true
+                    }
+                },
+                (Some(_), _) => {
+                    person.state = PersonState::Idling;
+                    true
+                }
+                (None, _) => false,
+            };
+            let player_x = match player_x {
+                Some(player_x) if facing_player => player_x,
+                _ => {
+                    gun_orders.push((entity, direction.x, None));
+                    continue;
+                }
+            };
+            direction.x = if player_x < position.x {
+                Directions::Left
+            } else {
+                Directions::Right
+            };
+
+            let shoot =
+                blackboard.can_shoot && rng.gen::<f32>() < SHOOT_FACTOR && fire_budget.take();
+            let shot = if !shoot {
+                None
+            } else if rng.gen::<f32>() < enemy.archetype.accuracy {
+                Some(0.)
+            } else {
+                Some(MISS_OFFSET)
+            };
+            gun_orders.push((entity, direction.x, shot));
+        }
+
+        // 4. point the guns the same way and pull the trigger, like the player's gun controls
+        for (gun, child, direction) in (&mut guns, &children, &mut directions).join() {
+            let order = gun_orders
+                .iter()
+                .find(|(parent, _facing, _shot)| *parent == child.parent);
+            if let Some((_parent, facing, shot)) = order {
+                direction.x = *facing;
+                gun.state = if let Some(aim_offset) = shot {
+                    gun.aim_offset = *aim_offset;
+                    gun.last_shot_seconds = current_time;
+                    GunState::Shooting
+                } else if (current_time - gun.last_shot_seconds) < SHOT_SECONDS {
+                    GunState::Shooting
+                } else {
+                    gun.spawned_bullet = false;
+                    GunState::Holstered
+                };
+            }
+        }
+    }
+}
+
+/// One of the archetype's tactics, holding his ground when he doesn't have any.
+fn pick_tactic(tactics: &[Tactic], rng: &mut StdRng) -> Tactic {
+    if tactics.is_empty() {
+        return Tactic::Hold;
+    }
+    tactics[rng.gen_range(0, tactics.len())]
+}
+
+/// Whether a wall is between someone at `from` and someone at `to` on the same floor.
+fn blocks_view(wall: &Collider, from: Vector2<f32>, to: Vector2<f32>) -> bool {
+    let bbox = &wall.bounding_box;
+    let (left, right) = if from.x < to.x {
+        (from.x, to.x)
+    } else {
+        (to.x, from.x)
+    };
+    bbox.position.x + bbox.half_size.x > left
+        && bbox.position.x - bbox.half_size.x < right
+        && (bbox.position.y - from.y).abs() < bbox.half_size.y
+}
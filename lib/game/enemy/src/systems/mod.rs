@@ -1,3 +1,5 @@
 mod ai;
+mod combat;
 
 pub use ai::EnemyAISystem;
+pub use combat::EnemyCombatSystem;
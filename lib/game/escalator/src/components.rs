@@ -0,0 +1,64 @@
+use amethyst::{
+    core::math::Vector2,
+    ecs::{Component, DenseVecStorage, Entity},
+};
+
+/// A diagonal moving staircase between two neighbouring floors.
+///
+/// `bottom` and `top` are the points where the steps meet the floor surfaces.
+#[derive(Component)]
+#[storage(DenseVecStorage)]
+pub struct Escalator {
+    pub bottom: Vector2<f32>,
+    pub top: Vector2<f32>,
+}
+
+impl Escalator {
+    pub fn new(bottom: Vector2<f32>, top: Vector2<f32>) -> Self {
+        Escalator { bottom, top }
+    }
+
+    /// Where a ride starts, the bottom on the way up and the top on the way down.
+    pub fn start(&self, going_up: bool) -> Vector2<f32> {
+        if going_up {
+            self.bottom
+        } else {
+            self.top
+        }
+    }
+
+    /// Where a ride ends.
+    pub fn end(&self, going_up: bool) -> Vector2<f32> {
+        self.start(!going_up)
+    }
+
+    /// The unit vector a rider moves along.
+    pub fn direction(&self, going_up: bool) -> Vector2<f32> {
+        (self.end(going_up) - self.start(going_up)).normalize()
+    }
+}
+
+/// Which way a person wants to take an escalator, set by the player's input or the enemy AI.
+#[derive(Component, Default)]
+#[storage(DenseVecStorage)]
+pub struct EscalatorControl {
+    pub up: bool,
+    pub down: bool,
+}
+
+/// A person being carried by an escalator.
+#[derive(Component)]
+#[storage(DenseVecStorage)]
+pub struct EscalatorRide {
+    pub escalator: Entity,
+    pub going_up: bool,
+}
+
+impl EscalatorRide {
+    pub fn new(escalator: Entity, going_up: bool) -> Self {
+        EscalatorRide {
+            escalator,
+            going_up,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use amethyst::{
+    core::{math::Vector2, Named},
+    ecs::{Entities, Entity, LazyUpdate, ReadExpect},
+};
+
+use crate::components::Escalator;
+use floors::Floor;
+
+/// The steps are part of the building tiles, the entity only knows where they go.
+pub fn load_escalator(
+    id: usize,
+    entities: &Entities,
+    lazy_update: &ReadExpect<LazyUpdate>,
+    bottom: Vector2<f32>,
+    top: Vector2<f32>,
+    floors_overlapped: &Vec<usize>,
+) {
+    let escalator_entity: Entity = entities.create();
+    lazy_update.insert(escalator_entity, Named::new("Escalator"));
+    lazy_update.insert(escalator_entity, Escalator::new(bottom, top));
+    lazy_update.insert(
+        escalator_entity,
+        Floor::new(vec![id], floors_overlapped.clone()),
+    );
+}
@@ -0,0 +1,5 @@
+pub mod components;
+pub mod systems;
+
+mod entity;
+pub use self::entity::load_escalator;
@@ -0,0 +1,3 @@
+mod ride;
+
+pub use self::ride::EscalatorSystem;
@@ -0,0 +1,137 @@
+use amethyst::{
+    core::math::Vector2,
+    ecs::{Entities, Entity, Join, ReadStorage, System, WriteStorage},
+};
+
+use crate::components::{Escalator, EscalatorControl, EscalatorRide};
+use person::components::{Person, PersonState};
+use physics::components::{Collider, Direction, Directions, Motion};
+
+const ESCALATOR_SPEED: f32 = 24.0;
+// how close the feet have to be to the end of the steps to get on
+const FOOT_DISTANCE: f32 = 4.0;
+const SAFE_PADDING: f32 = 0.1;
+
+pub struct EscalatorSystem;
+
+impl<'s> System<'s> for EscalatorSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Escalator>,
+        ReadStorage<'s, EscalatorControl>,
+        WriteStorage<'s, EscalatorRide>,
+        WriteStorage<'s, Person>,
+        WriteStorage<'s, Collider>,
+        WriteStorage<'s, Motion>,
+        WriteStorage<'s, Direction>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            escalators,
+            controls,
+            mut rides,
+            mut persons,
+            mut colliders,
+            mut motions,
+            mut directions,
+        ) = data;
+
+        // carry the riders along, and let them off at the end
+        let mut finished: Vec<Entity> = Vec::new();
+        for (entity, ride, person, collider, motion) in (
+            &entities,
+            &rides,
+            &mut persons,
+            &mut colliders,
+            &mut motions,
+        )
+            .join()
+        {
+            // shot, respawned or the escalator is no longer drawn
+            let escalator = match escalators.get(ride.escalator) {
+                Some(escalator) if person.state == PersonState::RidingEscalator => escalator,
+                _ => {
+                    if person.state == PersonState::RidingEscalator {
+                        person.state = PersonState::Idling;
+                    }
+                    finished.push(entity);
+                    continue;
+                }
+            };
+            let bbox = &mut collider.bounding_box;
+            let end = standing_at(escalator.end(ride.going_up), bbox.half_size.y);
+            let direction = escalator.direction(ride.going_up);
+            if (end - bbox.position).dot(&direction) <= 0. {
+                bbox.position = end;
+                bbox.old_position = end;
+                motion.velocity = Vector2::new(0., 0.);
+                person.state = PersonState::Idling;
+                collider.on_ground = true;
+                finished.push(entity);
+            } else {
+                motion.velocity = direction * ESCALATOR_SPEED;
+            }
+        }
+        for entity in finished {
+            rides.remove(entity);
+        }
+
+        // get on at the foot of the steps
+        let mut started: Vec<(Entity, EscalatorRide)> = Vec::new();
+        for (entity, control, person, collider, motion, direction, _) in (
+            &entities,
+            &controls,
+            &mut persons,
+            &mut colliders,
+            &mut motions,
+            &mut directions,
+            !&rides,
+        )
+            .join()
+        {
+            if !(control.up || control.down) || !collider.on_ground || !can_board(person.state) {
+                continue;
+            }
+            let bbox = &mut collider.bounding_box;
+            let feet = Vector2::new(bbox.position.x, bbox.position.y - bbox.half_size.y);
+            for (escalator_entity, escalator) in (&entities, &escalators).join() {
+                let going_up = control.up;
+                let start = escalator.start(going_up);
+                if (feet.x - start.x).abs() <= FOOT_DISTANCE
+                    && (feet.y - start.y).abs() <= FOOT_DISTANCE
+                {
+                    let ride_direction = escalator.direction(going_up);
+                    bbox.position = standing_at(start, bbox.half_size.y);
+                    bbox.old_position = bbox.position;
+                    motion.velocity = ride_direction * ESCALATOR_SPEED;
+                    direction.x = if ride_direction.x < 0. {
+                        Directions::Left
+                    } else {
+                        Directions::Right
+                    };
+                    person.state = PersonState::RidingEscalator;
+                    collider.on_ground = false;
+                    started.push((entity, EscalatorRide::new(escalator_entity, going_up)));
+                    break;
+                }
+            }
+        }
+        for (entity, ride) in started {
+            let _ = rides.insert(entity, ride);
+        }
+    }
+}
+
+fn can_board(state: PersonState) -> bool {
+    match state {
+        PersonState::Idling | PersonState::Walking | PersonState::Ducking => true,
+        _ => false,
+    }
+}
+
+/// The center of a person's bounding box when his feet are at `point`.
+fn standing_at(point: Vector2<f32>, half_height: f32) -> Vector2<f32> {
+    Vector2::new(point.x, point.y + half_height + SAFE_PADDING)
+}
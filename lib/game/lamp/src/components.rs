@@ -0,0 +1,47 @@
+use amethyst::ecs::{Component, DenseVecStorage, NullStorage};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LampState {
+    Hanging,
+    Falling,
+    Broken,
+}
+
+/// A lamp hanging from the ceiling, shooting it brings it down on whoever is underneath and
+/// puts the lights out on its floor.
+#[derive(Component)]
+#[storage(DenseVecStorage)]
+pub struct Lamp {
+    /// id of the object in the map's "lamps" layer
+    pub id: usize,
+    pub floor: usize,
+    pub state: LampState,
+    pub velocity: f32,
+    /// the player gets the points for anybody it lands on
+    pub dropped_by_player: bool,
+}
+
+impl Lamp {
+    pub fn new(id: usize, floor: usize) -> Self {
+        Lamp {
+            id,
+            floor,
+            state: LampState::Hanging,
+            velocity: 0.,
+            dropped_by_player: false,
+        }
+    }
+
+    pub fn shoot_down(&mut self, by_player: bool) {
+        if self.state == LampState::Hanging {
+            self.state = LampState::Falling;
+            self.dropped_by_player = by_player;
+        }
+    }
+}
+
+/// Marks something drawn dark because the lights are out on its floor, so only the tints put on
+/// for the dark are taken off again once the lights come back on.
+#[derive(Component, Default)]
+#[storage(NullStorage)]
+pub struct Darkened;
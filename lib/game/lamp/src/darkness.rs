@@ -0,0 +1,33 @@
+/// The floors whose lights were put out by a falling lamp, and for how much longer.
+///
+/// The `MapRenderSystem` draws those floors dark, only silhouettes and muzzle flashes show.
+#[derive(Debug, Default)]
+pub struct Darkness {
+    floors: Vec<(usize, f32)>,
+}
+
+impl Darkness {
+    /// Puts the lights out on a floor, a floor that is already dark stays dark for longer.
+    pub fn darken(&mut self, floor: usize, seconds: f32) {
+        match self.floors.iter_mut().find(|(f, _)| *f == floor) {
+            Some((_, seconds_left)) => *seconds_left = seconds_left.max(seconds),
+            None => self.floors.push((floor, seconds)),
+        }
+    }
+
+    /// Moves the clock on, the lights come back on once the time is up.
+    pub fn update(&mut self, seconds: f32) {
+        for (_, seconds_left) in self.floors.iter_mut() {
+            *seconds_left -= seconds;
+        }
+        self.floors.retain(|(_, seconds_left)| *seconds_left > 0.);
+    }
+
+    pub fn is_dark(&self, floor: usize) -> bool {
+        self.floors.iter().any(|(f, _)| *f == floor)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.floors.is_empty()
+    }
+}
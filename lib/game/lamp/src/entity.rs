@@ -0,0 +1,50 @@
+use amethyst::{
+    core::{math::Vector2, Named, Transform},
+    ecs::{Entities, Entity, LazyUpdate, ReadExpect},
+    renderer::{sprite::SpriteSheetHandle, transparent::Transparent, SpriteRender},
+};
+
+use crate::components::Lamp;
+use floors::Floor;
+use physics::components::Collider;
+
+const LAMP_WIDTH: f32 = 8.;
+const LAMP_HEIGHT: f32 = 10.;
+
+/// `position` is the middle of the lamp, it hangs on the first of `floors_overlapped`.
+pub fn load_lamp(
+    id: usize,
+    entities: &Entities,
+    lazy_update: &ReadExpect<LazyUpdate>,
+    sprite_sheet_handle: SpriteSheetHandle,
+    position: Vector2<f32>,
+    floors_overlapped: &Vec<usize>,
+) {
+    // bullets are checked against the lamp by the lamp system, nobody bumps into it
+    let mut collider = Collider::new(LAMP_WIDTH, LAMP_HEIGHT);
+    collider.is_collidable = false;
+    collider.allow_proximity = false;
+    collider.bounding_box.position = position;
+    collider.bounding_box.old_position = position;
+    let mut transform = Transform::default();
+    // in front of the doors
+    transform.set_translation_xyz(position.x, position.y, 0.3);
+
+    let lamp_entity: Entity = entities.create();
+    lazy_update.insert(lamp_entity, Named::new("Lamp"));
+    lazy_update.insert(
+        lamp_entity,
+        Lamp::new(id, floors_overlapped.first().cloned().unwrap_or(0)),
+    );
+    lazy_update.insert(lamp_entity, collider);
+    lazy_update.insert(lamp_entity, transform);
+    lazy_update.insert(
+        lamp_entity,
+        SpriteRender {
+            sprite_sheet: sprite_sheet_handle,
+            sprite_number: 0,
+        },
+    );
+    lazy_update.insert(lamp_entity, Transparent);
+    lazy_update.insert(lamp_entity, Floor::new(vec![id], floors_overlapped.clone()));
+}
@@ -0,0 +1,7 @@
+pub mod components;
+pub mod systems;
+
+mod darkness;
+mod entity;
+pub use self::darkness::Darkness;
+pub use self::entity::load_lamp;
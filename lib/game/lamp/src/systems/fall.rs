@@ -0,0 +1,181 @@
+use amethyst::{
+    core::{timing::Time, Named, Transform},
+    ecs::{Entities, Entity, Join, Read, ReadStorage, System, Write, WriteStorage},
+    renderer::SpriteRender,
+    shrev::EventChannel,
+};
+
+use crate::components::{Lamp, LampState};
+use crate::Darkness;
+use floors::FloorsDrawn;
+use level::LevelProgress;
+use person::{
+    components::{Bullet, Gun, Person, PersonState},
+    despawn_bullet,
+};
+use physics::components::{Collidee, Collider, GenericBox};
+use score::{Bounty, KillCause, ScoreEvent};
+
+const FALL_ACCELERATION: f32 = 300.0;
+// how long the lights stay out on the floor a lamp fell on
+const DARK_SECONDS: f32 = 4.0;
+// the broken lamp is a lot flatter than the hanging one
+const BROKEN_HALF_HEIGHT: f32 = 2.0;
+const BROKEN_SPRITE: usize = 1;
+
+/// Brings down the lamps that get shot, kills whoever they land on and puts the lights out on
+/// their floor.
+pub struct LampSystem;
+
+impl<'s> System<'s> for LampSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Lamp>,
+        WriteStorage<'s, Collider>,
+        ReadStorage<'s, Collidee>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, SpriteRender>,
+        WriteStorage<'s, Bullet>,
+        WriteStorage<'s, Gun>,
+        WriteStorage<'s, Person>,
+        ReadStorage<'s, Named>,
+        ReadStorage<'s, Bounty>,
+        Read<'s, FloorsDrawn>,
+        Read<'s, Time>,
+        Write<'s, Darkness>,
+        Write<'s, LevelProgress>,
+        Write<'s, EventChannel<ScoreEvent>>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            mut lamps,
+            mut colliders,
+            collidees,
+            mut transforms,
+            mut sprites,
+            mut bullets,
+            mut guns,
+            mut persons,
+            names,
+            bounties,
+            floors_drawn,
+            time,
+            mut darkness,
+            mut level_progress,
+            mut score_events,
+        ) = data;
+        let delta_seconds = time.delta_seconds();
+        darkness.update(delta_seconds);
+
+        // 1. bullets that hit a hanging lamp, the ones that hit something else are already gone
+        for (bullet_entity, bullet, bullet_collider, collidee) in
+            (&entities, &mut bullets, &colliders, &collidees).join()
+        {
+            if bullet.spent || collidee.horizontal.is_some() {
+                continue;
+            }
+            for (lamp, lamp_collider) in (&mut lamps, &colliders).join() {
+                if lamp.state == LampState::Hanging
+                    && bullet_collider.is_overlapping_with(lamp_collider, false)
+                {
+                    let shot_by_player = bullet
+                        .parent
+                        .and_then(|parent| guns.get(parent))
+                        .map_or(false, |gun| gun.is_player);
+                    println!("### Lamp {} shot down ###", lamp.id);
+                    lamp.shoot_down(shot_by_player);
+                    despawn_bullet(&entities, &mut guns, bullet_entity, bullet);
+                    break;
+                }
+            }
+        }
+
+        // 2. what a falling lamp can land on, and who it can land on
+        let grounds: Vec<GenericBox> = (&colliders, &names)
+            .join()
+            .filter(|(collider, named)| {
+                collider.is_collidable
+                    && !collider.is_person
+                    && named.name != "wall"
+                    && named.name != "Bullet"
+            })
+            .map(|(collider, _named)| collider.bounding_box.clone())
+            .collect();
+        let people: Vec<(Entity, GenericBox)> = (&entities, &persons, &colliders)
+            .join()
+            .map(|(entity, _person, collider)| (entity, collider.bounding_box.clone()))
+            .collect();
+
+        // 3. move the falling lamps
+        for (lamp, collider, transform, sprite) in
+            (&mut lamps, &mut colliders, &mut transforms, &mut sprites).join()
+        {
+            if lamp.state != LampState::Falling {
+                continue;
+            }
+            let bbox = &mut collider.bounding_box;
+            let old_bottom = bbox.position.y - bbox.half_size.y;
+            // the highest thing underneath, or the bottom of the lamp's floor
+            let floor_bottom = floors_drawn
+                .floor_boundaries
+                .iter()
+                .find(|boundary| boundary.floor_number == lamp.floor)
+                .map_or(old_bottom, |boundary| {
+                    boundary.position.y - boundary.half_size.y
+                });
+            let ground_y = grounds
+                .iter()
+                .filter(|ground| {
+                    (ground.position.x - bbox.position.x).abs()
+                        < ground.half_size.x + bbox.half_size.x
+                        && ground.position.y + ground.half_size.y <= old_bottom
+                })
+                .map(|ground| ground.position.y + ground.half_size.y)
+                .fold(floor_bottom, f32::max);
+
+            lamp.velocity -= FALL_ACCELERATION * delta_seconds;
+            bbox.old_position = bbox.position;
+            bbox.position.y += lamp.velocity * delta_seconds;
+
+            for (person_entity, person_box) in people.iter() {
+                let hit = (person_box.position.x - bbox.position.x).abs()
+                    < person_box.half_size.x + bbox.half_size.x
+                    && (person_box.position.y - bbox.position.y).abs()
+                        < person_box.half_size.y + bbox.half_size.y;
+                if !hit {
+                    continue;
+                }
+                if let Some(person) = persons.get_mut(*person_entity) {
+                    if person.state == PersonState::Dying || person.state.is_in_room() {
+                        continue;
+                    }
+                    let name = names
+                        .get(*person_entity)
+                        .map_or("", |named| named.name.as_ref());
+                    println!("### {} hit by a lamp ###", name);
+                    if lamp.dropped_by_player && name == "Enemy" {
+                        score_events.single_write(ScoreEvent::enemy_killed(
+                            KillCause::Lamp,
+                            bounties.get(*person_entity),
+                        ));
+                    }
+                    person.kill(KillCause::Lamp);
+                }
+            }
+
+            if bbox.position.y - bbox.half_size.y <= ground_y {
+                bbox.half_size.y = BROKEN_HALF_HEIGHT;
+                bbox.position.y = ground_y + BROKEN_HALF_HEIGHT;
+                lamp.state = LampState::Broken;
+                lamp.velocity = 0.;
+                sprite.sprite_number = BROKEN_SPRITE;
+                println!("### Lights out on floor {} ###", lamp.floor);
+                darkness.darken(lamp.floor, DARK_SECONDS);
+                level_progress.break_lamp(lamp.id);
+            }
+            transform.set_translation_y(bbox.position.y);
+        }
+    }
+}
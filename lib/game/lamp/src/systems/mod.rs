@@ -0,0 +1,3 @@
+mod fall;
+
+pub use self::fall::LampSystem;
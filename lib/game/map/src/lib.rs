@@ -1,6 +1,10 @@
 mod map;
 mod tileset;
+mod tmx;
+mod validation;
 
 pub mod systems;
-pub use self::map::{Layer, Map};
+pub use self::map::{Layer, Map, Object, Property, PropertyValue, Tile, TilesetRef};
 pub use self::tileset::Tileset;
+pub use self::tmx::{TmxFormat, TsxFormat};
+pub use self::validation::{validate, Problem};
@@ -1,6 +1,12 @@
+use std::{cmp::Ordering, f32::consts::PI};
+
 use amethyst::{
     assets::{Asset, Handle, Prefab, ProcessingState},
-    core::{math::Vector2, transform::Transform, Named, WithNamed},
+    core::{
+        math::{Vector2, Vector3},
+        transform::Transform,
+        Named, WithNamed,
+    },
     ecs::{Builder, Entities, Entity, LazyUpdate, ReadExpect, VecStorage, World, WorldExt},
     error::Error,
     renderer::{sprite::SpriteSheetHandle, SpriteRender, SpriteSheet},
@@ -9,21 +15,137 @@ use amethyst::{
 use serde::{Deserialize, Serialize};
 
 use animation::components::AnimationPrefabData;
-use door::load_door;
+use door::{load_door, DoorSpawn};
 use elevator::load_elevator;
+use enemy::{Link, NavigationGraph};
+use escalator::load_escalator;
 use floors::{Floor, FloorsDrawn};
+use lamp::load_lamp;
 use physics::components::{Collider, Direction, Motion};
 
 const OFFSET_X: f32 = 0.0;
 const OFFSET_Y: f32 = 224.0;
-const TILE_WIDTH: f32 = 256.0;
-const TILE_HEIGHT: f32 = 48.0;
-const NUM_COLUMNS: usize = 1;
+const TILE_Z: f32 = -10.0;
+// how far above the bottom of a floor the collision objects it stands on can start
+const FLOOR_DEPTH: f32 = 8.0;
+// the narrowest gap between the collision objects of a floor a person can drop through
+const MIN_OPENING_WIDTH: f32 = 16.0;
 
-#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+// the top three bits of a gid are used by Tiled to flip the tile
+const FLIPPED_HORIZONTALLY_FLAG: u32 = 0x8000_0000;
+const FLIPPED_VERTICALLY_FLAG: u32 = 0x4000_0000;
+const FLIPPED_DIAGONALLY_FLAG: u32 = 0x2000_0000;
+
+/// The value of a custom property, Tiled stores colors and files as strings.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum PropertyValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Property {
     pub name: String,
-    pub value: usize,
+    #[serde(rename = "type", default)]
+    pub property_type: String,
+    pub value: PropertyValue,
+}
+
+impl Property {
+    pub fn as_usize(&self) -> Option<usize> {
+        match self.value {
+            PropertyValue::Int(value) if value >= 0 => Some(value as usize),
+            PropertyValue::Float(value) if value >= 0. => Some(value as usize),
+            _ => None,
+        }
+    }
+
+    pub fn as_f32(&self) -> Option<f32> {
+        match self.value {
+            PropertyValue::Int(value) => Some(value as f32),
+            PropertyValue::Float(value) => Some(value as f32),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.value {
+            PropertyValue::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            PropertyValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the color as rgba, Tiled writes them as `#AARRGGBB` (or `#RRGGBB`).
+    pub fn as_color(&self) -> Option<[f32; 4]> {
+        let hex = self.as_str()?.trim_start_matches('#');
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        let channel = |shift: u32| ((value >> shift) & 0xff) as f32 / 255.;
+        match hex.len() {
+            6 => Some([channel(16), channel(8), channel(0), 1.]),
+            8 => Some([channel(16), channel(8), channel(0), channel(24)]),
+            _ => None,
+        }
+    }
+}
+
+/// A tile from a tile layer, with the flip flags separated from the gid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tile {
+    pub gid: u32,
+    pub flipped_horizontally: bool,
+    pub flipped_vertically: bool,
+    pub flipped_diagonally: bool,
+}
+
+impl Tile {
+    /// Returns `None` for empty tiles (a gid of 0).
+    pub fn from_raw_gid(raw_gid: u32) -> Option<Tile> {
+        let gid = raw_gid
+            & !(FLIPPED_HORIZONTALLY_FLAG | FLIPPED_VERTICALLY_FLAG | FLIPPED_DIAGONALLY_FLAG);
+        if gid == 0 {
+            return None;
+        }
+        Some(Tile {
+            gid,
+            flipped_horizontally: raw_gid & FLIPPED_HORIZONTALLY_FLAG != 0,
+            flipped_vertically: raw_gid & FLIPPED_VERTICALLY_FLAG != 0,
+            flipped_diagonally: raw_gid & FLIPPED_DIAGONALLY_FLAG != 0,
+        })
+    }
+
+    /// Applies the flips to the transform, Tiled flips diagonally first and then horizontally
+    /// and vertically.
+    fn apply_flips(&self, transform: &mut Transform) {
+        let mut scale = Vector3::new(1., 1., 1.);
+        if self.flipped_diagonally {
+            // a diagonal flip is a horizontal flip followed by a quarter turn, which swaps
+            // the axes of the other flips
+            scale.x = if self.flipped_vertically { 1. } else { -1. };
+            scale.y = if self.flipped_horizontally { -1. } else { 1. };
+            transform.set_rotation_2d(PI / 2.);
+        } else {
+            scale.x = if self.flipped_horizontally { -1. } else { 1. };
+            scale.y = if self.flipped_vertically { -1. } else { 1. };
+        }
+        transform.set_scale(scale);
+    }
+}
+
+/// A reference to an external tileset, the first gid is the gid of its first tile.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct TilesetRef {
+    pub firstgid: u32,
+    pub source: String,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
@@ -43,16 +165,40 @@ pub struct Object {
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct Layer {
     pub name: String,
-    pub data: Option<Vec<i32>>,
+    pub data: Option<Vec<u32>>,
     pub height: Option<i32>,
-    pub opacity: i32,
+    pub opacity: f32,
     pub width: Option<i32>,
     pub x: f32,
     pub y: f32,
+    #[serde(default)]
+    pub offsetx: f32,
+    #[serde(default)]
+    pub offsety: f32,
     pub visible: bool,
     pub objects: Option<Vec<Object>>,
 }
 
+impl Layer {
+    /// The x position of the left side of an object in this layer.
+    pub fn object_x(&self, obj: &Object) -> f32 {
+        OFFSET_X + self.offsetx + obj.x
+    }
+
+    /// The y position of the top of an object in this layer, remember y is reversed.
+    pub fn object_y(&self, obj: &Object) -> f32 {
+        OFFSET_Y - self.offsety - obj.y
+    }
+}
+
+impl Object {
+    pub fn get_property(&self, name: &str) -> Option<&Property> {
+        self.properties
+            .as_ref()
+            .and_then(|properties| properties.iter().find(|p| p.name == name))
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct Map {
     pub width: i32,
@@ -60,6 +206,8 @@ pub struct Map {
     pub tilewidth: i32,
     pub tileheight: i32,
     pub layers: Vec<Layer>,
+    #[serde(default)]
+    pub tilesets: Vec<TilesetRef>,
 }
 
 impl From<Map> for Result<ProcessingState<Map>, Error> {
@@ -77,17 +225,127 @@ impl Asset for Map {
 impl Map {
     pub fn init_floors(&mut self, world: &mut World) {
         // set a new floors drawn resource
+        let floors = self.build_floors();
+        self.add_floors_to_layers(&floors);
+        world.insert(floors);
+        world.insert(self.build_navigation());
+    }
+
+    /// Creates the `NavigationGraph` enemies use to get between floors, the objects need to
+    /// know their floors first (see `add_floors_to_layers`).
+    pub fn build_navigation(&self) -> NavigationGraph {
+        let mut navigation = NavigationGraph::default();
+        if let Some(layer) = self.get_layer("elevators") {
+            for obj in layer.objects.iter().flatten() {
+                let floor_property = |name| obj.get_property(name).and_then(Property::as_usize);
+                if let (Some(min_floor), Some(max_floor)) =
+                    (floor_property("min_floor"), floor_property("max_floor"))
+                {
+                    navigation.add_link(Link::Elevator {
+                        x: layer.object_x(obj) + (obj.width / 2.),
+                        min_floor,
+                        max_floor,
+                    });
+                }
+            }
+        }
+        if let Some(layer) = self.get_layer("escalators") {
+            for obj in layer.objects.iter().flatten() {
+                let bottom_floor = obj
+                    .floors_overlapped
+                    .as_ref()
+                    .and_then(|floors| floors.iter().min().cloned());
+                if let Some(bottom_floor) = bottom_floor {
+                    let (bottom, top) = escalator_ends(layer, obj);
+                    navigation.add_link(Link::Escalator {
+                        bottom,
+                        top,
+                        bottom_floor,
+                    });
+                }
+            }
+        }
+        for (top_floor, x) in self.stairs() {
+            navigation.add_link(Link::Stairs { x, top_floor });
+        }
+        if let Some(layer) = self.get_layer("doors") {
+            for obj in layer.objects.iter().flatten() {
+                for floor in obj.floors_overlapped.iter().flatten() {
+                    navigation.add_door(*floor, layer.object_x(obj) + (obj.width / 2.));
+                }
+            }
+        }
+        navigation
+    }
+
+    /// The floors with stairs down to the floor below, which are openings between the collision
+    /// objects they stand on, with the middle of the widest opening of each.
+    pub(crate) fn stairs(&self) -> Vec<(usize, f32)> {
+        let layer = match self.get_layer("floors") {
+            Some(layer) => layer,
+            None => return Vec::new(),
+        };
+        layer
+            .objects
+            .iter()
+            .flatten()
+            .filter_map(|obj| {
+                let floor = obj.get_property("floor").and_then(Property::as_usize)?;
+                if floor == 0 {
+                    return None;
+                }
+                self.opening_x(layer, obj).map(|x| (floor, x))
+            })
+            .collect()
+    }
+
+    /// The middle of the widest gap between the collision objects a floor stands on, the sides
+    /// of the building don't count.
+    fn opening_x(&self, floor_layer: &Layer, floor_obj: &Object) -> Option<f32> {
+        let left = floor_layer.object_x(floor_obj);
+        let right = left + floor_obj.width;
+        let bottom_y = floor_layer.object_y(floor_obj) - floor_obj.height;
+        let mut solid: Vec<(f32, f32)> = Vec::new();
+        if let Some(layer) = self.get_layer("collision") {
+            for obj in layer.objects.iter().flatten() {
+                let (x, top_y) = (layer.object_x(obj), layer.object_y(obj));
+                if obj.name != "wall"
+                    && top_y >= bottom_y
+                    && top_y <= bottom_y + FLOOR_DEPTH
+                    && x < right
+                    && x + obj.width > left
+                {
+                    solid.push((x, x + obj.width));
+                }
+            }
+        }
+        solid.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+        let mut widest: Option<(f32, f32)> = None;
+        let mut solid_end: Option<f32> = None;
+        for (solid_left, solid_right) in solid {
+            if let Some(opening_left) = solid_end {
+                let width = widest.map_or(MIN_OPENING_WIDTH, |(start, end)| end - start);
+                if solid_left - opening_left >= width {
+                    widest = Some((opening_left, solid_left));
+                }
+            }
+            solid_end = Some(solid_end.map_or(solid_right, |end| end.max(solid_right)));
+        }
+        widest.map(|(start, end)| (start + end) / 2.)
+    }
+
+    /// Creates a `FloorsDrawn` with the boundaries of every floor in the "floors" layer.
+    pub fn build_floors(&self) -> FloorsDrawn {
         let mut floors = FloorsDrawn::default();
         for layer in self.layers.iter() {
             if layer.name == "floors" {
                 self.load_floor_boundaries(layer, &mut floors);
             }
         }
-        world.insert(floors);
-        self.add_floors_to_layers(world);
+        floors
     }
 
-    pub fn add_floors_to_layers(&mut self, world: &mut World) {
+    pub fn add_floors_to_layers(&mut self, floors: &FloorsDrawn) {
         for layer in self.layers.iter_mut() {
             match layer.name.as_ref() {
                 "floors" => {
@@ -95,16 +353,15 @@ impl Map {
                 }
                 _ => {
                     // loop through objects and add their list of floors
+                    let (offsetx, offsety) = (layer.offsetx, layer.offsety);
                     if let Some(objects) = &mut layer.objects {
                         for obj in objects.iter_mut() {
-                            let x = OFFSET_X + obj.x + (obj.width / 2.);
-                            let y = OFFSET_Y - obj.y - (obj.height / 2.);
+                            let x = OFFSET_X + offsetx + obj.x + (obj.width / 2.);
+                            let y = OFFSET_Y - offsety - obj.y - (obj.height / 2.);
                             let position = Vector2::new(x, y);
-                            let floors_overlapped: Vec<usize> = {
-                                let floors = world.read_resource::<FloorsDrawn>();
-                                // do some work to find the floor number
-                                floors.find_floors(position, obj.width, obj.height)
-                            };
+                            // do some work to find the floor number
+                            let floors_overlapped: Vec<usize> =
+                                floors.find_floors(position, obj.width, obj.height);
                             obj.floors_overlapped = match floors_overlapped.len() {
                                 0 => None,
                                 _ => Some(floors_overlapped),
@@ -120,19 +377,38 @@ impl Map {
         self.layers.iter().find(|l| l.name == layer_name)
     }
 
+    /// The number of red doors, each of them has documents behind it.
+    pub fn red_door_count(&self) -> usize {
+        self.get_layer("doors")
+            .and_then(|layer| layer.objects.as_ref())
+            .map(|objects| {
+                objects
+                    .iter()
+                    .filter(|obj| obj.name == "red_left" || obj.name == "red_right")
+                    .count()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns the index of the tileset that the gid belongs to and the tile number within it.
+    pub fn find_tileset(&self, gid: u32) -> Option<(usize, usize)> {
+        self.tilesets
+            .iter()
+            .enumerate()
+            .filter(|(_, tileset)| tileset.firstgid <= gid)
+            .max_by_key(|(_, tileset)| tileset.firstgid)
+            .map(|(index, tileset)| (index, (gid - tileset.firstgid) as usize))
+    }
+
     fn load_floor_boundaries(&self, layer: &Layer, floors: &mut FloorsDrawn) {
         if let Some(objects) = &layer.objects {
             for (_index, obj) in objects.iter().enumerate() {
-                let mut floor_number: usize = 0;
-                let x = OFFSET_X + obj.x + (obj.width / 2.);
-                let y = OFFSET_Y - obj.y - (obj.height / 2.);
-                if let Some(properties) = &obj.properties {
-                    for property in properties {
-                        if property.name == "floor" {
-                            floor_number = property.value as usize;
-                        }
-                    }
-                }
+                let x = layer.object_x(obj) + (obj.width / 2.);
+                let y = layer.object_y(obj) - (obj.height / 2.);
+                let floor_number = obj
+                    .get_property("floor")
+                    .and_then(Property::as_usize)
+                    .unwrap_or(0);
                 println!(
                     "Adding floor boundaries: floor: {}, x: {}, y: {}, width: {}, height: {}",
                     floor_number, obj.x, obj.y, obj.width, obj.height
@@ -167,8 +443,8 @@ impl Map {
                             if obj.name == "shaft" || obj.name == "entry" {
                                 collider.is_collidable = false;
                             }
-                            let x = OFFSET_X + obj.x;
-                            let y = OFFSET_Y - obj.y;
+                            let x = layer.object_x(obj);
+                            let y = layer.object_y(obj);
                             transform.set_translation_z(-10.0);
                             if let Some(floors_overlapped) = &obj.floors_overlapped {
                                 println!(
@@ -177,8 +453,8 @@ impl Map {
                                 );
                             }
                             bbox.position = Vector2::new(
-                                OFFSET_X + (obj.x * scale_x) + bbox.half_size.x,
-                                OFFSET_Y - (obj.y * scale_y) - bbox.half_size.y,
+                                OFFSET_X + layer.offsetx + (obj.x * scale_x) + bbox.half_size.x,
+                                OFFSET_Y - layer.offsety - (obj.y * scale_y) - bbox.half_size.y,
                             );
                             bbox.old_position = bbox.position;
                             lazy_update
@@ -206,6 +482,7 @@ impl Map {
         prefab_handle: Handle<Prefab<AnimationPrefabData>>,
         floors_to_draw: &Vec<usize>,
         rendered_ids: &mut Vec<usize>,
+        used_door_ids: &[usize],
     ) {
         if let Some(layer) = self.get_layer("doors") {
             if let Some(objects) = &layer.objects {
@@ -214,22 +491,26 @@ impl Map {
                         if !rendered_ids.contains(&obj.id)
                             && should_draw(floors_overlapped, floors_to_draw)
                         {
-                            let x = OFFSET_X + obj.x + (obj.width / 2.);
+                            let x = layer.object_x(obj) + (obj.width / 2.);
                             // FIXME: need to figure out why doors are off by 1 pixel
-                            let y = OFFSET_Y - obj.y - (obj.height / 2.) - 1.;
+                            let y = layer.object_y(obj) - (obj.height / 2.) - 1.;
                             println!(
                                 "### Adding door object {}, x: {}, y: {}, width: {}, height: {} ###",
                                 obj.name, x, y, obj.width, obj.height
                             );
                             rendered_ids.push(obj.id);
                             load_door(
-                                obj.id,
                                 entities,
                                 lazy_update,
                                 prefab_handle.clone(),
-                                Vector2::new(x, y),
-                                &obj.name,
-                                &floors_overlapped,
+                                DoorSpawn {
+                                    id: obj.id,
+                                    position: Vector2::new(x, y),
+                                    name: &obj.name,
+                                    floors_overlapped: &floors_overlapped,
+                                    pickup: obj.get_property("weapon").and_then(Property::as_str),
+                                    is_used: used_door_ids.contains(&obj.id),
+                                },
                             );
                         }
                     }
@@ -253,22 +534,16 @@ impl Map {
                         if !rendered_ids.contains(&obj.id)
                             && should_draw(floors_overlapped, floors_to_draw)
                         {
-                            let x = OFFSET_X + obj.x + (obj.width / 2.);
-                            let y = OFFSET_Y - obj.y - (48. / 2.);
-                            let mut min_floor: usize = 0;
-                            let mut max_floor: usize = 0;
-                            let mut start_floor: usize = 0;
-                            if let Some(properties) = &obj.properties {
-                                for property in properties {
-                                    if property.name == "min_floor" {
-                                        min_floor = property.value;
-                                    } else if property.name == "max_floor" {
-                                        max_floor = property.value;
-                                    } else if property.name == "start_floor" {
-                                        start_floor = property.value;
-                                    }
-                                }
-                            }
+                            let x = layer.object_x(obj) + (obj.width / 2.);
+                            let y = layer.object_y(obj) - (48. / 2.);
+                            let floor_property = |name| {
+                                obj.get_property(name)
+                                    .and_then(Property::as_usize)
+                                    .unwrap_or(0)
+                            };
+                            let min_floor = floor_property("min_floor");
+                            let max_floor = floor_property("max_floor");
+                            let start_floor = floor_property("start_floor");
                             println!(
                                 "### Adding elevator object {:?}, x: {}, y: {}, min: {}, max: {}, start: {} ###",
                                 obj, x, y, min_floor, max_floor, start_floor,
@@ -296,34 +571,147 @@ impl Map {
         }
     }
 
-    pub fn render_tiles(&self, world: &mut World, sprite_sheet_handle: &Handle<SpriteSheet>) {
-        // TODO: support drawing in different directions
-        // TODO: support different tileset spacing and margins
-        // get the ssprite sheet handle
-        if let Some(layer) = self.get_layer("map") {
-            if let Some(data) = &layer.data {
-                for (index, d) in data.iter().enumerate() {
-                    let x = TILE_WIDTH / 2.0 + TILE_HEIGHT * (index % NUM_COLUMNS) as f32; // offset is half tile width
-                    let y =
-                        OFFSET_Y - TILE_HEIGHT / 2.0 - (TILE_HEIGHT * (index / NUM_COLUMNS) as f32);
-
-                    let tile_sprite = SpriteRender {
-                        sprite_sheet: sprite_sheet_handle.clone(),
-                        sprite_number: (*d - 1) as usize, // sprite numbers are off by 1 in t
-                    };
-                    let mut tile_transform = Transform::default();
-                    // TODO: make these not hardcoded
-                    tile_transform.set_translation_xyz(x, y, -10.0);
-                    world
-                        .create_entity()
-                        .named("map_tile")
-                        .with(tile_transform)
-                        .with(tile_sprite)
-                        .build();
+    pub fn render_escalators(
+        &self,
+        entities: &Entities,
+        lazy_update: &ReadExpect<LazyUpdate>,
+        floors_to_draw: &Vec<usize>,
+        rendered_ids: &mut Vec<usize>,
+    ) {
+        if let Some(layer) = self.get_layer("escalators") {
+            if let Some(objects) = &layer.objects {
+                for obj in objects.iter() {
+                    if let Some(floors_overlapped) = &obj.floors_overlapped {
+                        if !rendered_ids.contains(&obj.id)
+                            && should_draw(floors_overlapped, floors_to_draw)
+                        {
+                            let (bottom, top) = escalator_ends(layer, obj);
+                            println!(
+                                "### Adding escalator object {}, bottom: {:?}, top: {:?} ###",
+                                obj.id, bottom, top
+                            );
+                            rendered_ids.push(obj.id);
+                            load_escalator(
+                                obj.id,
+                                entities,
+                                lazy_update,
+                                bottom,
+                                top,
+                                floors_overlapped,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn render_lamps(
+        &self,
+        entities: &Entities,
+        lazy_update: &ReadExpect<LazyUpdate>,
+        sprite_sheet_handle: SpriteSheetHandle,
+        floors_to_draw: &Vec<usize>,
+        rendered_ids: &mut Vec<usize>,
+        broken_lamp_ids: &[usize],
+    ) {
+        if let Some(layer) = self.get_layer("lamps") {
+            if let Some(objects) = &layer.objects {
+                for obj in objects.iter() {
+                    if let Some(floors_overlapped) = &obj.floors_overlapped {
+                        // a lamp that was shot down stays down
+                        if !rendered_ids.contains(&obj.id)
+                            && !broken_lamp_ids.contains(&obj.id)
+                            && should_draw(floors_overlapped, floors_to_draw)
+                        {
+                            let x = layer.object_x(obj) + (obj.width / 2.);
+                            let y = layer.object_y(obj) - (obj.height / 2.);
+                            println!(
+                                "### Adding lamp object {}, x: {}, y: {}, floors: {:?} ###",
+                                obj.id, x, y, floors_overlapped
+                            );
+                            rendered_ids.push(obj.id);
+                            load_lamp(
+                                obj.id,
+                                entities,
+                                lazy_update,
+                                sprite_sheet_handle.clone(),
+                                Vector2::new(x, y),
+                                floors_overlapped,
+                            );
+                        }
+                    }
                 }
             }
         }
     }
+
+    /// Draws every visible tile layer, `sprite_sheets` has one sprite sheet for each of the
+    /// map's tilesets (in the same order).
+    pub fn render_tiles(&self, world: &mut World, sprite_sheets: &[Handle<SpriteSheet>]) {
+        // TODO: support drawing in different directions
+        let tile_width = self.tilewidth as f32;
+        let tile_height = self.tileheight as f32;
+        for (layer_index, layer) in self.layers.iter().enumerate() {
+            let data = match &layer.data {
+                Some(data) if layer.visible => data,
+                _ => continue,
+            };
+            let columns = layer.width.unwrap_or(self.width).max(1) as usize;
+            for (index, raw_gid) in data.iter().enumerate() {
+                let tile = match Tile::from_raw_gid(*raw_gid) {
+                    Some(tile) => tile,
+                    None => continue,
+                };
+                let (tileset_index, sprite_number) = match self.find_tileset(tile.gid) {
+                    Some(found) if found.0 < sprite_sheets.len() => found,
+                    _ => {
+                        println!("No tileset found for gid {}", tile.gid);
+                        continue;
+                    }
+                };
+                // position in tilesheet is based on corner not middle
+                let x = OFFSET_X
+                    + layer.offsetx
+                    + tile_width / 2.0
+                    + tile_width * (index % columns) as f32;
+                let y = OFFSET_Y
+                    - layer.offsety
+                    - tile_height / 2.0
+                    - tile_height * (index / columns) as f32;
+
+                let tile_sprite = SpriteRender {
+                    sprite_sheet: sprite_sheets[tileset_index].clone(),
+                    sprite_number,
+                };
+                let mut tile_transform = Transform::default();
+                tile.apply_flips(&mut tile_transform);
+                // later layers are drawn on top of earlier ones
+                tile_transform.set_translation_xyz(x, y, TILE_Z + layer_index as f32 * 0.01);
+                world
+                    .create_entity()
+                    .named("map_tile")
+                    .with(tile_transform)
+                    .with(tile_sprite)
+                    .build();
+            }
+        }
+    }
+}
+
+/// The bottom and top of an escalator's steps, on the lower and upper floor surfaces.
+/// The "direction" property is the way the steps go up, "right" unless it says "left".
+fn escalator_ends(layer: &Layer, obj: &Object) -> (Vector2<f32>, Vector2<f32>) {
+    let left = layer.object_x(obj);
+    let right = left + obj.width;
+    let top_y = layer.object_y(obj);
+    let bottom_y = top_y - obj.height;
+    let goes_left = obj.get_property("direction").and_then(Property::as_str) == Some("left");
+    if goes_left {
+        (Vector2::new(right, bottom_y), Vector2::new(left, top_y))
+    } else {
+        (Vector2::new(left, bottom_y), Vector2::new(right, top_y))
+    }
 }
 
 fn should_draw(floors_overlapped: &Vec<usize>, floors_to_draw: &Vec<usize>) -> bool {
@@ -396,3 +784,139 @@ fn should_draw(floors_overlapped: &Vec<usize>, floors_to_draw: &Vec<usize>) -> b
 //         }
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separates_flip_flags_from_gid() {
+        let tile = Tile::from_raw_gid(FLIPPED_HORIZONTALLY_FLAG | FLIPPED_DIAGONALLY_FLAG | 7);
+        assert_eq!(
+            tile,
+            Some(Tile {
+                gid: 7,
+                flipped_horizontally: true,
+                flipped_vertically: false,
+                flipped_diagonally: true,
+            })
+        );
+        assert_eq!(Tile::from_raw_gid(0), None);
+        assert_eq!(Tile::from_raw_gid(FLIPPED_VERTICALLY_FLAG), None);
+    }
+
+    #[test]
+    fn flips_tiles_by_scaling() {
+        let mut transform = Transform::default();
+        Tile::from_raw_gid(FLIPPED_HORIZONTALLY_FLAG | 1)
+            .unwrap()
+            .apply_flips(&mut transform);
+        assert_eq!(*transform.scale(), Vector3::new(-1., 1., 1.));
+
+        let mut transform = Transform::default();
+        Tile::from_raw_gid(FLIPPED_VERTICALLY_FLAG | 1)
+            .unwrap()
+            .apply_flips(&mut transform);
+        assert_eq!(*transform.scale(), Vector3::new(1., -1., 1.));
+    }
+
+    #[test]
+    fn flips_diagonally_with_a_quarter_turn() {
+        let mut transform = Transform::default();
+        Tile::from_raw_gid(FLIPPED_DIAGONALLY_FLAG | 1)
+            .unwrap()
+            .apply_flips(&mut transform);
+        assert_eq!(*transform.scale(), Vector3::new(-1., 1., 1.));
+        assert!((transform.rotation().angle() - PI / 2.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn finds_tileset_by_first_gid() {
+        let map = Map {
+            tilesets: vec![
+                TilesetRef {
+                    firstgid: 1,
+                    source: String::from("floors.tsx"),
+                },
+                TilesetRef {
+                    firstgid: 33,
+                    source: String::from("props.tsx"),
+                },
+            ],
+            ..Map::default()
+        };
+        assert_eq!(map.find_tileset(1), Some((0, 0)));
+        assert_eq!(map.find_tileset(32), Some((0, 31)));
+        assert_eq!(map.find_tileset(35), Some((1, 2)));
+        assert_eq!(map.find_tileset(0), None);
+    }
+
+    #[test]
+    fn reads_custom_properties() {
+        let property = |value| Property {
+            name: String::from("test"),
+            property_type: String::new(),
+            value,
+        };
+        assert_eq!(property(PropertyValue::Int(3)).as_usize(), Some(3));
+        assert_eq!(property(PropertyValue::Int(-3)).as_usize(), None);
+        assert_eq!(property(PropertyValue::Float(1.5)).as_f32(), Some(1.5));
+        assert_eq!(property(PropertyValue::Bool(true)).as_bool(), Some(true));
+        assert_eq!(
+            property(PropertyValue::String(String::from("#ff0000"))).as_color(),
+            Some([1., 0., 0., 1.])
+        );
+        assert_eq!(
+            property(PropertyValue::String(String::from("#80ffffff"))).as_color(),
+            Some([1., 1., 1., 128. / 255.])
+        );
+        assert_eq!(property(PropertyValue::Int(1)).as_str(), None);
+    }
+
+    #[test]
+    fn finds_the_widest_opening_a_floor_stands_on() {
+        let object = |name: &str, bounds: [f32; 4], floor: Option<i64>| Object {
+            name: name.to_string(),
+            x: bounds[0],
+            y: bounds[1],
+            width: bounds[2],
+            height: bounds[3],
+            properties: floor.map(|floor| {
+                vec![Property {
+                    name: String::from("floor"),
+                    property_type: String::from("int"),
+                    value: PropertyValue::Int(floor),
+                }]
+            }),
+            ..Object::default()
+        };
+        let layer = |name: &str, objects| Layer {
+            name: name.to_string(),
+            objects: Some(objects),
+            ..Layer::default()
+        };
+        let map = Map {
+            layers: vec![
+                layer(
+                    "floors",
+                    vec![
+                        object("floor_1", [0., 48., 256., 48.], Some(1)),
+                        object("floor_0", [0., 96., 256., 48.], Some(0)),
+                    ],
+                ),
+                layer(
+                    "collision",
+                    vec![
+                        object("wall", [32., 40., 4., 100.], None),
+                        object("floor", [36., 90., 60., 3.], None),
+                        object("floor", [170., 90., 50., 3.], None),
+                        object("shaft", [120., 90., 20., 3.], None),
+                        object("floor", [36., 138., 184., 3.], None),
+                    ],
+                ),
+            ],
+            ..Map::default()
+        };
+        assert_eq!(map.stairs(), vec![(1, 155.)]);
+    }
+}
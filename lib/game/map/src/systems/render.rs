@@ -1,23 +1,35 @@
+use amethyst::core::math::Vector2;
 use amethyst::core::timing::Time;
-use amethyst::core::{Named, SystemDesc};
-use amethyst::derive::SystemDesc;
+use amethyst::core::{Named, Transform};
 use amethyst::ecs::{
-    Entities, Join, LazyUpdate, Read, ReadExpect, ReadStorage, System, SystemData, World, Write,
+    Entities, Join, LazyUpdate, Read, ReadExpect, ReadStorage, System, Write, WriteStorage,
 };
+use amethyst::renderer::{palette::Srgba, resources::Tint};
 
 const REMOVE_WAIT_TIME: f64 = 1.0;
 const MAX_FLOOR: usize = 31;
 const MIN_FLOOR: usize = 0;
 const FLOORS_TO_RENDER: usize = 5;
+// with the lights out people are only silhouettes and everything else is black
+const SILHOUETTE_TINT: (f32, f32, f32) = (0.2, 0.2, 0.35);
+const DARK_TINT: (f32, f32, f32) = (0., 0., 0.);
 
 use crate::Map;
 use array_tool::vec::Intersect;
 use asset::{AssetType, PrefabList, SpriteSheetList};
 use floors::{Floor, FloorsDrawn};
+use lamp::{components::Darkened, Darkness};
+use level::LevelProgress;
+use person::components::{Gun, GunState, Person};
+use physics::components::Motion;
 use player::components::Player;
 
-#[derive(SystemDesc)]
-pub struct MapRenderSystem;
+#[derive(Default)]
+pub struct MapRenderSystem {
+    /// the dark floors being drawn and the floors that started being drawn, last frame
+    dark_floors: Vec<usize>,
+    newly_drawn: Vec<usize>,
+}
 
 impl<'s> System<'s> for MapRenderSystem {
     type SystemData = (
@@ -31,6 +43,14 @@ impl<'s> System<'s> for MapRenderSystem {
         Read<'s, PrefabList>,
         Read<'s, SpriteSheetList>,
         ReadExpect<'s, LazyUpdate>,
+        Read<'s, LevelProgress>,
+        Read<'s, Darkness>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Person>,
+        ReadStorage<'s, Gun>,
+        ReadStorage<'s, Motion>,
+        WriteStorage<'s, Tint>,
+        WriteStorage<'s, Darkened>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -45,6 +65,14 @@ impl<'s> System<'s> for MapRenderSystem {
             prefab_list,
             sprite_sheet_list,
             lazy_update,
+            level_progress,
+            darkness,
+            transforms,
+            persons,
+            guns,
+            motions,
+            mut tints,
+            mut darkened,
         ) = data;
 
         // determine which floor the player is on
@@ -103,6 +131,7 @@ impl<'s> System<'s> for MapRenderSystem {
             prefab_list.get(AssetType::Door).unwrap().clone(),
             &pending_draw,
             &mut rendered_ids,
+            &level_progress.used_doors(),
         );
         // TODO: NEED TO CHECK FIRST IF THE ELEVATOR IS ALREADY DRAWN
         // probably need to give every object an id and keep track of it that way
@@ -114,6 +143,15 @@ impl<'s> System<'s> for MapRenderSystem {
             &pending_draw,
             &mut rendered_ids,
         );
+        map.render_escalators(&entities, &lazy_update, &pending_draw, &mut rendered_ids);
+        map.render_lamps(
+            &entities,
+            &lazy_update,
+            sprite_sheet_list.get(AssetType::Lamp).unwrap().clone(),
+            &pending_draw,
+            &mut rendered_ids,
+            &level_progress.broken_lamps,
+        );
 
         // set the rendered floors to have the new ones
         let mut all_drawn = [floors_drawn.rendered_floors.clone(), floors_to_draw.clone()].concat();
@@ -154,6 +192,51 @@ impl<'s> System<'s> for MapRenderSystem {
             }
         }
 
+        // put the lights out on the dark floors being drawn, a gun shows while it's firing
+        let dark_floors: Vec<usize> = floors_to_draw
+            .iter()
+            .filter(|f| darkness.is_dark(**f))
+            .copied()
+            .collect();
+        // everything on a floor that just went dark, or whose things were just created, is
+        // looked at once, after that only what moves or flashes and what was darkened before
+        let changed_floors = dark_floors
+            .iter()
+            .any(|f| !self.dark_floors.contains(f) || self.newly_drawn.contains(f));
+        if !dark_floors.is_empty() || (&darkened).join().next().is_some() {
+            for (entity, transform) in (&entities, &transforms).join() {
+                let can_change = motions.contains(entity) || guns.contains(entity);
+                if !changed_floors && !can_change && !darkened.contains(entity) {
+                    continue;
+                }
+                let position = Vector2::new(transform.translation().x, transform.translation().y);
+                let in_dark = floors_drawn
+                    .find_floors(position, 0., 0.)
+                    .iter()
+                    .any(|f| dark_floors.contains(f));
+                let tint = match guns.get(entity) {
+                    _ if !in_dark => None,
+                    Some(gun) if gun.state == GunState::Shooting => None,
+                    Some(_gun) => Some(SILHOUETTE_TINT),
+                    None if persons.contains(entity) => Some(SILHOUETTE_TINT),
+                    None => Some(DARK_TINT),
+                };
+                match tint {
+                    Some((red, green, blue)) => {
+                        let _ = tints.insert(entity, Tint(Srgba::new(red, green, blue, 1.0)));
+                        let _ = darkened.insert(entity, Darkened);
+                    }
+                    None => {
+                        if darkened.remove(entity).is_some() {
+                            tints.remove(entity);
+                        }
+                    }
+                }
+            }
+        }
+        self.dark_floors = dark_floors;
+        self.newly_drawn = pending_draw;
+
         floors_drawn.rendered_ids = rendered_ids;
         floors_drawn.pending_removal = still_pending;
         floors_drawn.rendered_floors = floors_to_draw;
@@ -11,15 +11,15 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct Tileset {
-    columns: f32,
-    image: String,
-    imageheight: f32,
-    imagewidth: f32,
-    margin: f32,
-    spacing: f32,
-    tileheight: f32,
-    tilewidth: f32,
-    tilecount: f32,
+    pub(crate) columns: f32,
+    pub(crate) image: String,
+    pub(crate) imageheight: f32,
+    pub(crate) imagewidth: f32,
+    pub(crate) margin: f32,
+    pub(crate) spacing: f32,
+    pub(crate) tileheight: f32,
+    pub(crate) tilewidth: f32,
+    pub(crate) tilecount: f32,
 }
 
 impl From<Tileset> for Result<ProcessingState<Tileset>, Error> {
@@ -35,6 +35,14 @@ impl Asset for Tileset {
 }
 
 impl Tileset {
+    /// Makes the image path relative to the assets directory, Tiled stores it relative to the
+    /// directory of the tileset file.
+    pub fn relative_to(&mut self, directory: &str) {
+        if !directory.is_empty() {
+            self.image = format!("{}/{}", directory, self.image);
+        }
+    }
+
     pub fn load_spritesheet(&self, world: &mut World) -> Handle<SpriteSheet> {
         println!("Loaded tileset with image: {}", self.image);
 
@@ -0,0 +1,270 @@
+use amethyst::{assets::Format, error::Error};
+use roxmltree::{Document, Node};
+
+use crate::map::{Layer, Map, Object, Property, PropertyValue, TilesetRef};
+use crate::tileset::Tileset;
+
+/// Loads a `Map` straight from a Tiled `.tmx` file instead of the exported json.
+///
+/// Only csv encoded tile layers and external tilesets are supported.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TmxFormat;
+
+impl Format<Map> for TmxFormat {
+    fn name(&self) -> &'static str {
+        "TMX"
+    }
+
+    fn import_simple(&self, bytes: Vec<u8>) -> Result<Map, Error> {
+        let text = String::from_utf8(bytes)?;
+        let document = Document::parse(&text)?;
+        let root = document.root_element();
+
+        let mut map = Map {
+            width: parse_attribute(root, "width", 0),
+            height: parse_attribute(root, "height", 0),
+            tilewidth: parse_attribute(root, "tilewidth", 0),
+            tileheight: parse_attribute(root, "tileheight", 0),
+            ..Map::default()
+        };
+        for node in root.children().filter(Node::is_element) {
+            match node.tag_name().name() {
+                "tileset" => map.tilesets.push(TilesetRef {
+                    firstgid: parse_attribute(node, "firstgid", 1),
+                    source: node
+                        .attribute("source")
+                        .ok_or_else(|| Error::from_string("Embedded tilesets are not supported"))?
+                        .to_string(),
+                }),
+                "layer" => {
+                    let mut layer = parse_layer(node);
+                    layer.width = Some(parse_attribute(node, "width", map.width));
+                    layer.height = Some(parse_attribute(node, "height", map.height));
+                    layer.data = Some(parse_data(node)?);
+                    map.layers.push(layer);
+                }
+                "objectgroup" => {
+                    let mut layer = parse_layer(node);
+                    layer.objects = Some(
+                        node.children()
+                            .filter(|child| child.has_tag_name("object"))
+                            .map(parse_object)
+                            .collect::<Result<Vec<Object>, Error>>()?,
+                    );
+                    map.layers.push(layer);
+                }
+                _ => {}
+            }
+        }
+        Ok(map)
+    }
+}
+
+/// Loads a `Tileset` from a Tiled `.tsx` file. The image path is relative to the `.tsx` file,
+/// see `Tileset::relative_to`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TsxFormat;
+
+impl Format<Tileset> for TsxFormat {
+    fn name(&self) -> &'static str {
+        "TSX"
+    }
+
+    fn import_simple(&self, bytes: Vec<u8>) -> Result<Tileset, Error> {
+        let text = String::from_utf8(bytes)?;
+        let document = Document::parse(&text)?;
+        let root = document.root_element();
+        let image = root
+            .children()
+            .find(|child| child.has_tag_name("image"))
+            .ok_or_else(|| Error::from_string("Tileset has no image"))?;
+
+        Ok(Tileset {
+            columns: parse_attribute(root, "columns", 1.),
+            image: image.attribute("source").unwrap_or_default().to_string(),
+            imageheight: parse_attribute(image, "height", 0.),
+            imagewidth: parse_attribute(image, "width", 0.),
+            margin: parse_attribute(root, "margin", 0.),
+            spacing: parse_attribute(root, "spacing", 0.),
+            tileheight: parse_attribute(root, "tileheight", 0.),
+            tilewidth: parse_attribute(root, "tilewidth", 0.),
+            tilecount: parse_attribute(root, "tilecount", 0.),
+        })
+    }
+}
+
+fn parse_attribute<T: std::str::FromStr>(node: Node, name: &str, default: T) -> T {
+    node.attribute(name)
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+fn parse_layer(node: Node) -> Layer {
+    Layer {
+        name: node.attribute("name").unwrap_or_default().to_string(),
+        opacity: parse_attribute(node, "opacity", 1.),
+        visible: parse_attribute::<i32>(node, "visible", 1) != 0,
+        offsetx: parse_attribute(node, "offsetx", 0.),
+        offsety: parse_attribute(node, "offsety", 0.),
+        ..Layer::default()
+    }
+}
+
+fn parse_data(node: Node) -> Result<Vec<u32>, Error> {
+    let data = node
+        .children()
+        .find(|child| child.has_tag_name("data"))
+        .ok_or_else(|| Error::from_string("Tile layer has no data"))?;
+    if data.attribute("encoding") != Some("csv") {
+        return Err(Error::from_string(
+            "Only csv encoded tile layers are supported",
+        ));
+    }
+    data.text()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|gid| !gid.is_empty())
+        .map(|gid| {
+            gid.parse::<u32>()
+                .map_err(|_| Error::from_string(format!("Invalid gid: {}", gid)))
+        })
+        .collect()
+}
+
+fn parse_object(node: Node) -> Result<Object, Error> {
+    let properties: Vec<Property> = node
+        .children()
+        .filter(|child| child.has_tag_name("properties"))
+        .flat_map(|properties| properties.children())
+        .filter(|child| child.has_tag_name("property"))
+        .map(parse_property)
+        .collect::<Result<Vec<Property>, Error>>()?;
+    Ok(Object {
+        id: parse_attribute(node, "id", 0),
+        name: node.attribute("name").unwrap_or_default().to_string(),
+        height: parse_attribute(node, "height", 0.),
+        width: parse_attribute(node, "width", 0.),
+        rotation: parse_attribute(node, "rotation", 0.),
+        x: parse_attribute(node, "x", 0.),
+        y: parse_attribute(node, "y", 0.),
+        visible: parse_attribute::<i32>(node, "visible", 1) != 0,
+        properties: if properties.is_empty() {
+            None
+        } else {
+            Some(properties)
+        },
+        floors_overlapped: None,
+    })
+}
+
+fn parse_property(node: Node) -> Result<Property, Error> {
+    let name = node.attribute("name").unwrap_or_default().to_string();
+    let property_type = node.attribute("type").unwrap_or("string").to_string();
+    // multi-line strings are stored as text instead of a value
+    let raw_value = node
+        .attribute("value")
+        .or_else(|| node.text())
+        .unwrap_or_default();
+    let invalid = || {
+        Error::from_string(format!(
+            "Invalid {} property {}: {}",
+            property_type, name, raw_value
+        ))
+    };
+    let value = match property_type.as_ref() {
+        "bool" => PropertyValue::Bool(raw_value == "true"),
+        "int" => PropertyValue::Int(raw_value.parse().map_err(|_| invalid())?),
+        "float" => PropertyValue::Float(raw_value.parse().map_err(|_| invalid())?),
+        _ => PropertyValue::String(raw_value.to_string()),
+    };
+    Ok(Property {
+        name,
+        property_type,
+        value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::Tile;
+
+    const TMX: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.2" orientation="orthogonal" width="2" height="2" tilewidth="256" tileheight="48">
+ <tileset firstgid="1" source="floors.tsx"/>
+ <tileset firstgid="17" source="props.tsx"/>
+ <layer id="1" name="map" width="2" height="2" offsetx="4">
+  <data encoding="csv">
+1,2147483650,
+0,17
+</data>
+ </layer>
+ <objectgroup id="2" name="elevators" offsety="-8">
+  <object id="3" name="elevator" x="16" y="32" width="24" height="96">
+   <properties>
+    <property name="min_floor" type="int" value="1"/>
+    <property name="max_floor" type="int" value="3"/>
+    <property name="lit" type="bool" value="true"/>
+    <property name="tint" type="color" value="#ff00ff00"/>
+    <property name="note" value="fast"/>
+   </properties>
+  </object>
+ </objectgroup>
+</map>
+"##;
+
+    #[test]
+    fn imports_tile_and_object_layers() {
+        let map = TmxFormat.import_simple(TMX.as_bytes().to_vec()).unwrap();
+        assert_eq!((map.width, map.height), (2, 2));
+        assert_eq!((map.tilewidth, map.tileheight), (256, 48));
+        assert_eq!(
+            map.tilesets
+                .iter()
+                .map(|t| t.firstgid)
+                .collect::<Vec<u32>>(),
+            vec![1, 17]
+        );
+
+        let tiles = map.get_layer("map").unwrap();
+        assert_eq!(tiles.offsetx, 4.);
+        assert_eq!(tiles.width, Some(2));
+        assert_eq!(tiles.data, Some(vec![1, 2_147_483_650, 0, 17]));
+
+        let elevators = map.get_layer("elevators").unwrap();
+        assert_eq!(elevators.offsety, -8.);
+        let elevator = &elevators.objects.as_ref().unwrap()[0];
+        assert_eq!((elevator.x, elevator.y), (16., 32.));
+        let property = |name| elevator.get_property(name).unwrap();
+        assert_eq!(property("min_floor").as_usize(), Some(1));
+        assert_eq!(property("max_floor").as_usize(), Some(3));
+        assert_eq!(property("lit").as_bool(), Some(true));
+        assert_eq!(property("tint").as_color(), Some([0., 1., 0., 1.]));
+        assert_eq!(property("note").as_str(), Some("fast"));
+    }
+
+    #[test]
+    fn keeps_flip_flags_of_tiles() {
+        let map = TmxFormat.import_simple(TMX.as_bytes().to_vec()).unwrap();
+        let data = map.get_layer("map").unwrap().data.clone().unwrap();
+        let tile = Tile::from_raw_gid(data[1]).unwrap();
+        assert_eq!(tile.gid, 2);
+        assert!(tile.flipped_horizontally);
+        assert!(!tile.flipped_vertically);
+    }
+
+    #[test]
+    fn rejects_properties_that_are_not_numbers() {
+        let bad_int = TMX.replace("type=\"int\" value=\"3\"", "type=\"int\" value=\"3rd\"");
+        assert!(TmxFormat.import_simple(bad_int.into_bytes()).is_err());
+        let bad_float = TMX.replace("type=\"int\" value=\"1\"", "type=\"float\" value=\"\"");
+        assert!(TmxFormat.import_simple(bad_float.into_bytes()).is_err());
+    }
+
+    #[test]
+    fn rejects_other_encodings() {
+        let base64 = TMX.replace("encoding=\"csv\"", "encoding=\"base64\"");
+        assert!(TmxFormat.import_simple(base64.into_bytes()).is_err());
+    }
+}
@@ -0,0 +1,477 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::map::{Map, Object, Property};
+
+const ELEVATOR_PROPERTIES: [&str; 3] = ["min_floor", "max_floor", "start_floor"];
+
+/// Something wrong with a level that would break it at runtime.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Problem {
+    MissingLayer {
+        layer: String,
+    },
+    MissingElevatorProperty {
+        id: usize,
+        property: String,
+    },
+    InconsistentElevator {
+        id: usize,
+        min_floor: usize,
+        max_floor: usize,
+        start_floor: usize,
+    },
+    DoorOutsideFloors {
+        id: usize,
+        name: String,
+    },
+    CollisionOutsideFloors {
+        id: usize,
+        name: String,
+    },
+    DuplicateFloor {
+        floor_number: usize,
+        ids: Vec<usize>,
+    },
+    UnreachableRedDoor {
+        id: usize,
+        floors: Vec<usize>,
+    },
+    BadEscalator {
+        id: usize,
+        floors: Vec<usize>,
+    },
+    BadLamp {
+        id: usize,
+        floors: Vec<usize>,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::MissingLayer { layer } => write!(f, "missing layer \"{}\"", layer),
+            Problem::MissingElevatorProperty { id, property } => write!(
+                f,
+                "elevator {} is missing the \"{}\" property",
+                id, property
+            ),
+            Problem::InconsistentElevator {
+                id,
+                min_floor,
+                max_floor,
+                start_floor,
+            } => write!(
+                f,
+                "elevator {} needs min_floor <= start_floor <= max_floor (min: {}, max: {}, start: {})",
+                id, min_floor, max_floor, start_floor
+            ),
+            Problem::DoorOutsideFloors { id, name } => {
+                write!(f, "door {} ({}) does not overlap any floor", id, name)
+            }
+            Problem::CollisionOutsideFloors { id, name } => write!(
+                f,
+                "collision object {} ({}) is outside every floor",
+                id, name
+            ),
+            Problem::DuplicateFloor { floor_number, ids } => write!(
+                f,
+                "floor {} is defined by more than one object: {:?}",
+                floor_number, ids
+            ),
+            Problem::UnreachableRedDoor { id, floors } => write!(
+                f,
+                "red door {} on floors {:?} can't be reached from the starting floor",
+                id, floors
+            ),
+            Problem::BadEscalator { id, floors } => write!(
+                f,
+                "escalator {} needs to join two neighbouring floors, it overlaps {:?}",
+                id, floors
+            ),
+            Problem::BadLamp { id, floors } => write!(
+                f,
+                "lamp {} needs to hang inside a single floor, it overlaps {:?}",
+                id, floors
+            ),
+        }
+    }
+}
+
+/// Checks a map for the mistakes that otherwise only show up while playing it.
+///
+/// The map's floors are assigned to its objects first, so it doesn't need to have been
+/// initialized. The player is expected to start on the top floor.
+pub fn validate(map: &Map) -> Vec<Problem> {
+    let mut map = map.clone();
+    let floors = map.build_floors();
+    map.add_floors_to_layers(&floors);
+
+    let mut problems = Vec::new();
+    for layer in ["floors", "collision", "doors", "elevators"].iter() {
+        if map.get_layer(layer).is_none() {
+            problems.push(Problem::MissingLayer {
+                layer: layer.to_string(),
+            });
+        }
+    }
+
+    // floors
+    let mut floor_ids: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for obj in layer_objects(&map, "floors") {
+        let floor_number = obj
+            .get_property("floor")
+            .and_then(Property::as_usize)
+            .unwrap_or(0);
+        floor_ids.entry(floor_number).or_default().push(obj.id);
+    }
+    for (floor_number, ids) in floor_ids.iter() {
+        if ids.len() > 1 {
+            problems.push(Problem::DuplicateFloor {
+                floor_number: *floor_number,
+                ids: ids.clone(),
+            });
+        }
+    }
+
+    // elevators (and escalators), the ranges of floors they connect
+    let mut shafts: Vec<(usize, usize)> = Vec::new();
+    for obj in layer_objects(&map, "elevators") {
+        let mut values = Vec::new();
+        for property in ELEVATOR_PROPERTIES.iter() {
+            match obj.get_property(property).and_then(Property::as_usize) {
+                Some(value) => values.push(value),
+                None => problems.push(Problem::MissingElevatorProperty {
+                    id: obj.id,
+                    property: property.to_string(),
+                }),
+            }
+        }
+        if let [min_floor, max_floor, start_floor] = values[..] {
+            if min_floor > max_floor || start_floor < min_floor || start_floor > max_floor {
+                problems.push(Problem::InconsistentElevator {
+                    id: obj.id,
+                    min_floor,
+                    max_floor,
+                    start_floor,
+                });
+            } else {
+                shafts.push((min_floor, max_floor));
+            }
+        }
+    }
+
+    // escalators are optional, each one joins the two floors it overlaps
+    for obj in layer_objects(&map, "escalators") {
+        let floors = obj.floors_overlapped.clone().unwrap_or_default();
+        match (floors.iter().min(), floors.iter().max()) {
+            (Some(min_floor), Some(max_floor))
+                if floors.len() == 2 && min_floor + 1 == *max_floor =>
+            {
+                shafts.push((*min_floor, *max_floor));
+            }
+            _ => problems.push(Problem::BadEscalator { id: obj.id, floors }),
+        }
+    }
+
+    // lamps are optional too, the lights go out on the one floor a lamp hangs on
+    for obj in layer_objects(&map, "lamps") {
+        let floors = obj.floors_overlapped.clone().unwrap_or_default();
+        if floors.len() != 1 {
+            problems.push(Problem::BadLamp { id: obj.id, floors });
+        }
+    }
+
+    for obj in layer_objects(&map, "collision") {
+        if obj.floors_overlapped.is_none() {
+            problems.push(Problem::CollisionOutsideFloors {
+                id: obj.id,
+                name: obj.name.clone(),
+            });
+        }
+    }
+
+    let stairs: Vec<usize> = map.stairs().into_iter().map(|(floor, _)| floor).collect();
+    let reachable = reachable_floors(floor_ids.keys().last().cloned(), &shafts, &stairs);
+    for obj in layer_objects(&map, "doors") {
+        match &obj.floors_overlapped {
+            None => problems.push(Problem::DoorOutsideFloors {
+                id: obj.id,
+                name: obj.name.clone(),
+            }),
+            Some(floors_overlapped) => {
+                let is_red = obj.name == "red_left" || obj.name == "red_right";
+                if is_red && !floors_overlapped.iter().any(|f| reachable.contains(f)) {
+                    problems.push(Problem::UnreachableRedDoor {
+                        id: obj.id,
+                        floors: floors_overlapped.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    problems
+}
+
+fn layer_objects<'a>(map: &'a Map, layer_name: &str) -> impl Iterator<Item = &'a Object> {
+    map.get_layer(layer_name)
+        .and_then(|layer| layer.objects.as_ref())
+        .into_iter()
+        .flat_map(|objects| objects.iter())
+}
+
+/// Every floor that can be reached from `start_floor` by riding elevators and escalators or
+/// taking the stairs down from the floors in `stairs`.
+fn reachable_floors(
+    start_floor: Option<usize>,
+    shafts: &[(usize, usize)],
+    stairs: &[usize],
+) -> BTreeSet<usize> {
+    let mut reachable = BTreeSet::new();
+    let mut pending: Vec<usize> = start_floor.into_iter().collect();
+    while let Some(floor) = pending.pop() {
+        if !reachable.insert(floor) {
+            continue;
+        }
+        for (min_floor, max_floor) in shafts.iter() {
+            if *min_floor <= floor && floor <= *max_floor {
+                pending.extend(*min_floor..=*max_floor);
+            }
+        }
+        if floor > 0 && stairs.contains(&floor) {
+            pending.push(floor - 1);
+        }
+    }
+    reachable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::{Layer, PropertyValue};
+
+    fn object(id: usize, name: &str, bounds: [f32; 4], properties: &[(&str, i64)]) -> Object {
+        let properties: Vec<Property> = properties
+            .iter()
+            .map(|(property, value)| Property {
+                name: property.to_string(),
+                property_type: String::from("int"),
+                value: PropertyValue::Int(*value),
+            })
+            .collect();
+        Object {
+            id,
+            name: name.to_string(),
+            x: bounds[0],
+            y: bounds[1],
+            width: bounds[2],
+            height: bounds[3],
+            visible: true,
+            properties: Some(properties),
+            ..Object::default()
+        }
+    }
+
+    fn layer(name: &str, objects: Vec<Object>) -> Layer {
+        Layer {
+            name: name.to_string(),
+            visible: true,
+            objects: Some(objects),
+            ..Layer::default()
+        }
+    }
+
+    /// Three floors with an elevator going through all of them and a red door at the bottom.
+    fn building() -> Map {
+        Map {
+            layers: vec![
+                layer(
+                    "floors",
+                    vec![
+                        object(1, "floor_2", [0., 0., 256., 48.], &[("floor", 2)]),
+                        object(2, "floor_1", [0., 48., 256., 48.], &[("floor", 1)]),
+                        object(3, "floor_0", [0., 96., 256., 48.], &[("floor", 0)]),
+                    ],
+                ),
+                layer(
+                    "collision",
+                    vec![object(4, "floor", [0., 130., 256., 3.], &[])],
+                ),
+                layer(
+                    "doors",
+                    vec![object(5, "red_left", [200., 110., 16., 28.], &[])],
+                ),
+                layer(
+                    "elevators",
+                    vec![object(
+                        6,
+                        "elevator",
+                        [100., 4., 24., 136.],
+                        &[("min_floor", 0), ("max_floor", 2), ("start_floor", 2)],
+                    )],
+                ),
+            ],
+            ..Map::default()
+        }
+    }
+
+    fn objects_mut<'a>(map: &'a mut Map, layer_name: &str) -> &'a mut Vec<Object> {
+        map.layers
+            .iter_mut()
+            .find(|layer| layer.name == layer_name)
+            .and_then(|layer| layer.objects.as_mut())
+            .unwrap()
+    }
+
+    #[test]
+    fn accepts_a_valid_building() {
+        assert_eq!(validate(&building()), Vec::new());
+    }
+
+    #[test]
+    fn reports_missing_layers() {
+        let mut map = building();
+        map.layers.retain(|layer| layer.name != "doors");
+        assert_eq!(
+            validate(&map),
+            vec![Problem::MissingLayer {
+                layer: String::from("doors")
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_bad_elevators() {
+        let mut map = building();
+        objects_mut(&mut map, "elevators").push(object(
+            7,
+            "elevator",
+            [40., 4., 24., 136.],
+            &[("min_floor", 0), ("max_floor", 2), ("start_floor", 3)],
+        ));
+        objects_mut(&mut map, "elevators").push(object(
+            8,
+            "elevator",
+            [160., 4., 24., 136.],
+            &[("min_floor", 0), ("start_floor", 2)],
+        ));
+        assert_eq!(
+            validate(&map),
+            vec![
+                Problem::InconsistentElevator {
+                    id: 7,
+                    min_floor: 0,
+                    max_floor: 2,
+                    start_floor: 3,
+                },
+                Problem::MissingElevatorProperty {
+                    id: 8,
+                    property: String::from("max_floor"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_objects_outside_floors() {
+        let mut map = building();
+        objects_mut(&mut map, "doors").push(object(7, "blue_left", [40., 300., 16., 28.], &[]));
+        objects_mut(&mut map, "collision").push(object(8, "wall", [40., 300., 4., 40.], &[]));
+        assert_eq!(
+            validate(&map),
+            vec![
+                Problem::CollisionOutsideFloors {
+                    id: 8,
+                    name: String::from("wall"),
+                },
+                Problem::DoorOutsideFloors {
+                    id: 7,
+                    name: String::from("blue_left"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_duplicate_floors() {
+        let mut map = building();
+        objects_mut(&mut map, "floors").push(object(
+            7,
+            "floor_1",
+            [0., 300., 256., 48.],
+            &[("floor", 1)],
+        ));
+        assert_eq!(
+            validate(&map),
+            vec![Problem::DuplicateFloor {
+                floor_number: 1,
+                ids: vec![2, 7],
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_bad_escalators_and_lamps() {
+        let mut map = building();
+        map.layers.push(layer(
+            "escalators",
+            vec![object(7, "escalator", [20., 20., 48., 100.], &[])],
+        ));
+        map.layers.push(layer(
+            "lamps",
+            vec![object(8, "lamp", [20., 40., 8., 16.], &[])],
+        ));
+        assert_eq!(
+            validate(&map),
+            vec![
+                Problem::BadEscalator {
+                    id: 7,
+                    floors: vec![2, 1, 0],
+                },
+                Problem::BadLamp {
+                    id: 8,
+                    floors: vec![2, 1],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_red_doors_on_floors_that_are_cut_off() {
+        let mut map = building();
+        // the elevator doesn't go down to the red door any more
+        objects_mut(&mut map, "elevators")[0] = object(
+            6,
+            "elevator",
+            [100., 4., 24., 88.],
+            &[("min_floor", 1), ("max_floor", 2), ("start_floor", 2)],
+        );
+        assert_eq!(
+            validate(&map),
+            vec![Problem::UnreachableRedDoor {
+                id: 5,
+                floors: vec![0],
+            }]
+        );
+
+        // a gap too narrow to drop through doesn't help
+        objects_mut(&mut map, "collision").push(object(7, "floor", [0., 90., 120., 3.], &[]));
+        objects_mut(&mut map, "collision").push(object(8, "floor", [128., 90., 128., 3.], &[]));
+        assert_eq!(validate(&map).len(), 1);
+
+        objects_mut(&mut map, "collision")[2].x = 160.;
+        assert_eq!(validate(&map), Vec::new());
+    }
+
+    #[test]
+    fn takes_the_stairs_down_from_floors_with_an_opening() {
+        let reachable = reachable_floors(Some(3), &[(2, 3)], &[]);
+        assert_eq!(reachable.into_iter().collect::<Vec<usize>>(), vec![2, 3]);
+        let reachable = reachable_floors(Some(3), &[(2, 3)], &[2, 1]);
+        assert_eq!(
+            reachable.into_iter().collect::<Vec<usize>>(),
+            vec![0, 1, 2, 3]
+        );
+    }
+}
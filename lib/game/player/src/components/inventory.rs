@@ -0,0 +1,9 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// What the player is carrying.
+#[derive(Component, Debug, Default)]
+#[storage(DenseVecStorage)]
+pub struct Inventory {
+    /// documents collected from red doors
+    pub documents: usize,
+}
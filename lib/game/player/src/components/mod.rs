@@ -1,3 +1,5 @@
+mod inventory;
 mod player;
 
+pub use self::inventory::Inventory;
 pub use self::player::Player;
@@ -8,6 +8,7 @@ pub struct Player {
     pub is_ducking: bool,
     pub last_jump_state: bool,
     pub jump_time: Option<f32>,
+    pub dying_seconds: f32,
 }
 
 impl Default for Player {
@@ -23,6 +24,7 @@ impl Player {
             is_ducking: false,
             last_jump_state: false,
             jump_time: None,
+            dying_seconds: 0.,
         }
     }
 }
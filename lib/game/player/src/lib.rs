@@ -1,5 +1,7 @@
 pub mod components;
 pub mod systems;
 
+mod lives;
 mod player;
+pub use self::lives::{Lives, RespawnPoints};
 pub use self::player::load_player;
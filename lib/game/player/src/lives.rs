@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use amethyst::core::math::Vector2;
+
+const START_LIVES: usize = 3;
+
+/// The lives left in the current game, including the one being played.
+#[derive(Debug)]
+pub struct Lives {
+    pub remaining: usize,
+}
+
+impl Default for Lives {
+    fn default() -> Self {
+        Lives {
+            remaining: START_LIVES,
+        }
+    }
+}
+
+impl Lives {
+    /// Loses a life, returns true when the player still has one left to respawn with.
+    pub fn lose(&mut self) -> bool {
+        self.remaining = self.remaining.saturating_sub(1);
+        println!("### Lost a life, {} left ###", self.remaining);
+        self.remaining > 0
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+/// The last place the player stood safely on each floor of the level, used to respawn him.
+#[derive(Debug, Default)]
+pub struct RespawnPoints {
+    points: HashMap<usize, Vector2<f32>>,
+    last_floor: Option<usize>,
+}
+
+impl RespawnPoints {
+    pub fn update(&mut self, floor: usize, position: Vector2<f32>) {
+        self.points.insert(floor, position);
+        self.last_floor = Some(floor);
+    }
+
+    /// The respawn point of the floor, or the most recent one when the floor has none.
+    pub fn find(&self, floor: Option<usize>) -> Option<(usize, Vector2<f32>)> {
+        floor
+            .filter(|floor| self.points.contains_key(floor))
+            .or(self.last_floor)
+            .map(|floor| (floor, self.points[&floor]))
+    }
+}
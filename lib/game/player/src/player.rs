@@ -7,11 +7,16 @@ use amethyst::{
     prelude::Builder,
 };
 
-use crate::components::Player;
+use crate::components::{Inventory, Player};
 use animation::components::{Animation, AnimationId, AnimationPrefabData};
+use elevator::components::ElevatorCall;
+use escalator::components::EscalatorControl;
 use floors::Floor;
 use hierarchy::components::Child;
-use person::components::{Gun, Person};
+use person::{
+    components::{Gun, Person},
+    Weapons,
+};
 use physics::components::{Collidee, Collider, Direction, Directions, Motion, Proximity};
 
 /// Initialises one player in the middle-ish space
@@ -41,11 +46,15 @@ pub fn load_player(
     // to hide the gun just make it a sprite that you can't see because it's all transparent!
     // create the gun first
     // Create a player entity.
+    // the player respawns instead of being removed
+    let mut person = Person::new();
+    person.remove_when_dead = false;
     let player = world
         .create_entity()
         .named("Player")
-        .with(Person::new())
+        .with(person)
         .with(Player::new())
+        .with(Inventory::default())
         .with(collider)
         .with(Collidee::default())
         .with(transform)
@@ -69,22 +78,30 @@ pub fn load_player(
             Directions::Neutral,
         ))
         .with(Proximity::default())
+        .with(EscalatorControl::default())
+        .with(ElevatorCall::default())
         .with(Floor::new(vec![0], vec![30, 31]))
         .build();
 
+    let mut gun = Gun::new(true);
+    if let Some(weapons) = world.try_fetch::<Weapons>() {
+        gun.equip(weapons.starting().clone());
+    }
     let mut gun_transform = Transform::default();
     gun_transform.set_translation_xyz(x, y, 0.7);
     world
         .create_entity()
         .named("Gun")
         .with(Child::new(player, 8., 2., 0.))
-        .with(Gun::new(true))
+        .with(gun)
         .with(gun_transform)
         .with(Animation::new(
             AnimationId::Holster,
             vec![
                 AnimationId::PersonShoot,
                 AnimationId::PersonJumpShoot,
+                AnimationId::MachineGunShoot,
+                AnimationId::ShotgunShoot,
                 AnimationId::Holster,
             ],
         ))
@@ -1,14 +1,22 @@
 use amethyst::core::timing::Time;
-use amethyst::core::SystemDesc;
+use amethyst::core::{Named, SystemDesc};
 use amethyst::derive::SystemDesc;
 use amethyst::ecs::{Entities, Join, Read, ReadStorage, System, SystemData, World, WriteStorage};
-use amethyst::input::{InputHandler, StringBindings};
 
 use crate::components::Player;
+use controls::ControlInput;
+use elevator::components::ElevatorCall;
+use escalator::components::EscalatorControl;
 use hierarchy::components::Child;
-use person::components::{Gun, GunState, Person, PersonState};
+use person::{
+    components::{Gun, GunState, Person, PersonState},
+    Weapons,
+};
 use physics::components::{Collider, Direction, Directions, Proximity};
 
+// how long the gun stays out after a shot
+const SHOT_SECONDS: f64 = 0.05;
+
 #[derive(SystemDesc)]
 pub struct PlayerGunControlsSystem;
 
@@ -20,25 +28,42 @@ impl<'s> System<'s> for PlayerGunControlsSystem {
         ReadStorage<'s, Player>,
         WriteStorage<'s, Gun>,
         ReadStorage<'s, Child>,
-        Read<'s, InputHandler<StringBindings>>,
+        Read<'s, ControlInput>,
+        Read<'s, Weapons>,
         Read<'s, Time>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (entities, mut directions, persons, players, mut guns, children, input, time) = data;
+        let (entities, mut directions, persons, players, mut guns, children, input, weapons, time) =
+            data;
+        let current_time = time.absolute_time_seconds();
 
         // TODO: remove the maybe's and break this up
         for (direction, gun, child) in (&mut directions, &mut guns, &children).join() {
             // check if this is a player gun
-            for (entity, _player, _person) in (&entities, &players, &persons).join() {
-                if entity == child.parent {
-                    let move_input = input.axis_value("move").expect("Move action exists");
-                    let shoot_input = input.action_is_down("shoot").expect("Shoot action exists");
+            for (entity, _player, person) in (&entities, &players, &persons).join() {
+                if entity == child.parent
+                    && person.state != PersonState::Dying
+                    && person.state != PersonState::KnockedDown
+                    && person.state != PersonState::RidingEscalator
+                    && !person.state.is_in_room()
+                {
+                    let move_input = input.movement;
+                    let shoot_input = input.shoot;
+
+                    // a used up gun is swapped for the one he started with
+                    if gun.is_empty() {
+                        gun.equip(weapons.starting().clone());
+                    }
+                    // an automatic keeps firing while the button is held down
+                    let pulled = shoot_input && (gun.weapon.automatic || !gun.last_shoot_state);
+                    let reloaded = current_time - gun.last_shot_seconds >= gun.weapon.fire_interval;
 
-                    gun.state = if shoot_input && !gun.last_shoot_state && gun.shots_fired < 3 {
-                        gun.last_shot_seconds = time.absolute_time_seconds();
+                    gun.state = if pulled && reloaded && gun.can_fire() {
+                        gun.last_shot_seconds = current_time;
+                        gun.spawned_bullet = false;
                         GunState::Shooting
-                    } else if (time.absolute_time_seconds() - gun.last_shot_seconds) < 0.05 {
+                    } else if (current_time - gun.last_shot_seconds) < SHOT_SECONDS {
                         GunState::Shooting
                     } else {
                         gun.spawned_bullet = false;
@@ -66,25 +91,55 @@ impl<'s> System<'s> for PlayerControlsSystem {
         ReadStorage<'s, Proximity>,
         WriteStorage<'s, Person>,
         WriteStorage<'s, Player>,
-        Read<'s, InputHandler<StringBindings>>,
+        WriteStorage<'s, EscalatorControl>,
+        WriteStorage<'s, ElevatorCall>,
+        ReadStorage<'s, Named>,
+        Read<'s, ControlInput>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (colliders, mut directions, proximities, mut persons, mut players, input) = data;
+        let (
+            colliders,
+            mut directions,
+            proximities,
+            mut persons,
+            mut players,
+            mut escalator_controls,
+            mut elevator_calls,
+            names,
+            input,
+        ) = data;
 
         // TODO: remove the maybe's and break this up
-        for (collider, direction, proximity, person, player) in (
+        for (collider, direction, proximity, person, player, escalator_control, elevator_call) in (
             &colliders,
             &mut directions,
             &proximities,
             &mut persons,
             &mut players,
+            &mut escalator_controls,
+            &mut elevator_calls,
         )
             .join()
         {
-            let move_input = input.axis_value("move").expect("Move action exists");
-            let jump_input = input.action_is_down("jump").expect("Jump action exists");
-            let down_input = input.action_is_down("down").expect("Down action exists");
+            escalator_control.up = input.up;
+            escalator_control.down = input.down;
+            elevator_call.up = input.up;
+            elevator_call.down = input.down;
+
+            // no controlling the player until he respawns, gets back up, comes out of a room or gets
+            // off the escalator
+            if person.state == PersonState::Dying
+                || person.state == PersonState::KnockedDown
+                || person.state == PersonState::RidingEscalator
+                || person.state.is_in_room()
+            {
+                continue;
+            }
+
+            let move_input = input.movement;
+            let jump_input = input.jump;
+            let down_input = input.down;
 
             // No changing directions when you hop
             if person.state != PersonState::Hopping {
@@ -99,10 +154,16 @@ impl<'s> System<'s> for PlayerControlsSystem {
                 player.is_ducking = false;
             }
 
+            // down moves the cab when he's inside, on its roof he can duck
+            let on_roof = collider
+                .riding
+                .and_then(|riding| names.get(riding))
+                .map_or(false, |named| named.name == "ElevatorTop");
+
             person.state = if jump_input && !player.last_jump_state {
                 PersonState::Jumping
             } else if collider.on_ground {
-                if down_input && !collider.on_elevator {
+                if down_input && (!collider.on_elevator || on_roof) {
                     if !player.is_ducking {
                         player.is_ducking = true;
                     }
@@ -0,0 +1,142 @@
+use amethyst::{
+    core::{timing::Time, Named, Transform},
+    ecs::{Entities, Join, Read, ReadStorage, System, Write, WriteStorage},
+    renderer::Camera,
+};
+
+use crate::{
+    components::Player,
+    lives::{Lives, RespawnPoints},
+};
+use animation::components::Animation;
+use floors::Floor;
+use hierarchy::components::Child;
+use level::Difficulty;
+use person::components::{Person, PersonState};
+use physics::components::{Collider, Motion};
+
+// long enough for the die animation to finish and for the player to notice
+const DEATH_SECONDS: f32 = 2.;
+// enemies this many floors away from the respawn point are removed
+const ENEMY_RESET_FLOORS: usize = 2;
+
+/// Keeps track of where the player can respawn and brings him back after he dies.
+pub struct PlayerDeathSystem;
+
+impl<'s> System<'s> for PlayerDeathSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Player>,
+        WriteStorage<'s, Person>,
+        WriteStorage<'s, Collider>,
+        WriteStorage<'s, Motion>,
+        WriteStorage<'s, Animation>,
+        ReadStorage<'s, Floor>,
+        ReadStorage<'s, Named>,
+        ReadStorage<'s, Child>,
+        ReadStorage<'s, Camera>,
+        WriteStorage<'s, Transform>,
+        Write<'s, Lives>,
+        Write<'s, RespawnPoints>,
+        Write<'s, Difficulty>,
+        Read<'s, Time>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            mut players,
+            mut persons,
+            mut colliders,
+            mut motions,
+            mut animations,
+            floors,
+            names,
+            children,
+            cameras,
+            mut transforms,
+            mut lives,
+            mut respawn_points,
+            mut difficulty,
+            time,
+        ) = data;
+
+        let mut respawned_at: Option<(usize, f32)> = None;
+        for (player, person, collider, motion, animation, floor) in (
+            &mut players,
+            &mut persons,
+            &mut colliders,
+            &mut motions,
+            &mut animations,
+            &floors,
+        )
+            .join()
+        {
+            if person.state != PersonState::Dying {
+                player.dying_seconds = 0.;
+                // standing still or walking on a single floor is safe
+                let is_safe = (person.state == PersonState::Idling
+                    || person.state == PersonState::Walking)
+                    && collider.on_ground
+                    && !collider.on_elevator;
+                if is_safe && floor.floors_overlapped.len() == 1 {
+                    respawn_points
+                        .update(floor.floors_overlapped[0], collider.bounding_box.position);
+                }
+                continue;
+            }
+
+            player.dying_seconds += time.delta_seconds();
+            if player.dying_seconds < DEATH_SECONDS || lives.is_game_over() {
+                continue;
+            }
+            difficulty.record_death();
+            if !lives.lose() {
+                // the game over is handled by the game state
+                continue;
+            }
+
+            let floor_number = floor.floors_overlapped.iter().cloned().min();
+            if let Some((floor_number, position)) = respawn_points.find(floor_number) {
+                println!(
+                    "### Respawning player on floor {} at {:?} ###",
+                    floor_number, position
+                );
+                let bbox = &mut collider.bounding_box;
+                bbox.position = position;
+                bbox.old_position = position;
+                person.update_position(position.x, position.y);
+                respawned_at = Some((floor_number, position.y));
+            }
+            motion.velocity.x = 0.;
+            motion.velocity.y = 0.;
+            person.state = PersonState::Idling;
+            person.killed_by = None;
+            player.dying_seconds = 0.;
+            // start animating again, the die animation stopped it
+            animation.show = true;
+        }
+
+        if let Some((floor_number, y)) = respawned_at {
+            // reset the enemies around the respawn point
+            for (entity, named, floor) in (&entities, &names, &floors).join() {
+                let is_nearby = floor
+                    .floors_overlapped
+                    .iter()
+                    .any(|f| (*f as i32 - floor_number as i32).abs() <= ENEMY_RESET_FLOORS as i32);
+                if named.name == "Enemy" && is_nearby {
+                    for (child_entity, child) in (&entities, &children).join() {
+                        if child.parent == entity {
+                            let _ = entities.delete(child_entity);
+                        }
+                    }
+                    let _ = entities.delete(entity);
+                }
+            }
+            // move the camera straight to the player instead of following him there
+            for (_camera, transform) in (&cameras, &mut transforms).join() {
+                transform.set_translation_y(y);
+            }
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use amethyst::{
+    core::timing::Time,
+    ecs::{Join, Read, ReadStorage, System, Write},
+};
+
+use crate::components::Player;
+use floors::{Floor, FloorsDrawn};
+use level::Difficulty;
+
+/// Ramps the difficulty up with the time spent on the level and how deep the player is.
+pub struct DifficultySystem;
+
+impl<'s> System<'s> for DifficultySystem {
+    type SystemData = (
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Floor>,
+        Read<'s, FloorsDrawn>,
+        Read<'s, Time>,
+        Write<'s, Difficulty>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (players, floors, floors_drawn, time, mut difficulty) = data;
+
+        let top_floor = floors_drawn
+            .floor_boundaries
+            .iter()
+            .map(|boundary| boundary.floor_number)
+            .max()
+            .unwrap_or(0);
+        let mut depth = difficulty.depth;
+        for (_player, floor) in (&players, &floors).join() {
+            if let Some(floor_number) = floor.floors_overlapped.iter().cloned().max() {
+                depth = top_floor.saturating_sub(floor_number);
+            }
+        }
+        difficulty.update(time.delta_seconds(), depth);
+    }
+}
@@ -0,0 +1,30 @@
+use amethyst::ecs::{Join, ReadStorage, System, Write};
+
+use crate::components::Player;
+use floors::Floor;
+use level::LevelProgress;
+
+/// Marks the level as finished when the player reaches the basement exit with every document.
+pub struct LevelExitSystem;
+
+impl<'s> System<'s> for LevelExitSystem {
+    type SystemData = (
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Floor>,
+        Write<'s, LevelProgress>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (players, floors, mut level_progress) = data;
+
+        if level_progress.reached_exit || !level_progress.has_all_documents() {
+            return;
+        }
+        for (_player, floor) in (&players, &floors).join() {
+            if floor.floors_overlapped == vec![level_progress.exit_floor] {
+                println!("### Player reached the exit ###");
+                level_progress.reached_exit = true;
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use amethyst::{
+    ecs::prelude::{Entity, Join, Read, ReadStorage, System, WriteStorage},
+    ui::{UiFinder, UiText},
+};
+
+use crate::lives::Lives;
+use person::components::Gun;
+
+/// Shows the lives left in the "lives_text" label of assets/ui/score.ron.
+#[derive(Default)]
+pub struct LivesHudSystem {
+    lives_display: Option<Entity>,
+}
+
+impl<'a> System<'a> for LivesHudSystem {
+    type SystemData = (Read<'a, Lives>, WriteStorage<'a, UiText>, UiFinder<'a>);
+
+    fn run(&mut self, (lives, mut ui_text, finder): Self::SystemData) {
+        // the label is created again with every level, so look it up until it is found
+        match self
+            .lives_display
+            .and_then(|entity| ui_text.get_mut(entity))
+        {
+            Some(lives_display) => lives_display.text = format!("LIVES {}", lives.remaining),
+            None => self.lives_display = finder.find("lives_text"),
+        }
+    }
+}
+
+/// Shows the player's weapon and the ammo left in it in the "weapon_text" label of
+/// assets/ui/score.ron.
+#[derive(Default)]
+pub struct WeaponHudSystem {
+    weapon_display: Option<Entity>,
+}
+
+impl<'a> System<'a> for WeaponHudSystem {
+    type SystemData = (ReadStorage<'a, Gun>, WriteStorage<'a, UiText>, UiFinder<'a>);
+
+    fn run(&mut self, (guns, mut ui_text, finder): Self::SystemData) {
+        let gun = match (&guns).join().find(|gun| gun.is_player) {
+            Some(gun) => gun,
+            None => return,
+        };
+        match self
+            .weapon_display
+            .and_then(|entity| ui_text.get_mut(entity))
+        {
+            Some(weapon_display) => {
+                let name = gun.weapon.name.replace('_', " ").to_uppercase();
+                weapon_display.text = match gun.ammo {
+                    Some(ammo) => format!("{} {}", name, ammo),
+                    None => name,
+                };
+            }
+            None => self.weapon_display = finder.find("weapon_text"),
+        }
+    }
+}
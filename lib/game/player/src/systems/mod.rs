@@ -1,6 +1,15 @@
 mod controls;
+mod death;
+mod difficulty;
+mod exit;
+mod hud;
 mod transformation;
 
 pub use self::controls::PlayerControlsSystem;
 pub use self::controls::PlayerGunControlsSystem;
+pub use self::death::PlayerDeathSystem;
+pub use self::difficulty::DifficultySystem;
+pub use self::exit::LevelExitSystem;
+pub use self::hud::LivesHudSystem;
+pub use self::hud::WeaponHudSystem;
 pub use self::transformation::CameraTransformationSystem;